@@ -0,0 +1,42 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Extend a partial path, which has a column chosen for every row before `row`, in every way that
+/// keeps each column and each box to a single cell. Finished paths are pushed to `output` in the
+/// same order that the library's `generate_paths` produces them.
+fn extend_path(row: usize, path: u128, cols: u16, boxes: u16, output: &mut Vec<u128>) {
+    if row == 9 {
+        output.push(path);
+        return;
+    }
+
+    for col in 0..9 {
+        let square = 3 * (row / 3) + col / 3;
+        if cols & (1 << col) != 0 || boxes & (1 << square) != 0 {
+            continue;
+        }
+
+        extend_path(
+            row + 1,
+            path | 1 << (9 * row + col),
+            cols | 1 << col,
+            boxes | 1 << square,
+            output,
+        );
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let mut paths = Vec::new();
+    extend_path(0, 0, 0, 0, &mut paths);
+
+    let mut source = String::from("[\n");
+    for path in paths {
+        writeln!(source, "    Bitfield::from_bits({:#x}),", path).unwrap();
+    }
+    source.push_str("]\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("paths.rs"), source).unwrap();
+}
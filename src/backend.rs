@@ -0,0 +1,98 @@
+use crate::{
+    backtrack::Backtracker,
+    dlx::DlxSolver,
+    solver::{SolveError, Solver},
+    Board,
+};
+
+/// A strategy for solving Sudoku. Every engine produces the same `Board` type, so callers can swap
+/// between them without caring how the solutions are found.
+pub trait SolverBackend {
+    /// The name used to select this engine, for example on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Lazily iterate through every solution to the given board.
+    fn solutions<'a>(&'a self, board: &Board) -> Box<dyn Iterator<Item = Board> + 'a>;
+
+    /// Find a solution to the given board.
+    fn solve(&self, board: &Board) -> Result<Board, SolveError> {
+        self.solutions(board).next().ok_or(SolveError::NoSolution)
+    }
+
+    /// Count the solutions to the given board, giving up once `limit` solutions have been found.
+    fn count_solutions(&self, board: &Board, limit: usize) -> usize {
+        self.solutions(board).take(limit).count()
+    }
+}
+
+impl SolverBackend for Solver {
+    fn name(&self) -> &'static str {
+        "templates"
+    }
+
+    fn solutions<'a>(&'a self, board: &Board) -> Box<dyn Iterator<Item = Board> + 'a> {
+        Box::new(Solver::solutions(self, board))
+    }
+}
+
+impl SolverBackend for Backtracker {
+    fn name(&self) -> &'static str {
+        "backtrack"
+    }
+
+    fn solutions<'a>(&'a self, board: &Board) -> Box<dyn Iterator<Item = Board> + 'a> {
+        Box::new(Backtracker::solutions(self, board))
+    }
+}
+
+impl SolverBackend for DlxSolver {
+    fn name(&self) -> &'static str {
+        "dlx"
+    }
+
+    fn solutions<'a>(&'a self, board: &Board) -> Box<dyn Iterator<Item = Board> + 'a> {
+        Box::new(DlxSolver::solutions(self, board))
+    }
+}
+
+/// Every available engine. The first one is the default.
+pub fn engines() -> Vec<Box<dyn SolverBackend>> {
+    vec![
+        Box::new(Solver::new()),
+        Box::new(Backtracker::new()),
+        Box::new(DlxSolver::new()),
+    ]
+}
+
+/// Look up an engine by its name.
+pub fn engine(name: &str) -> Option<Box<dyn SolverBackend>> {
+    engines().into_iter().find(|engine| engine.name() == name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{engine, engines};
+    use crate::{solver::TWO_SOLUTIONS, Board};
+
+    #[test]
+    fn looks_up_engines_by_name() {
+        for name in ["templates", "backtrack", "dlx"] {
+            assert_eq!(engine(name).unwrap().name(), name);
+        }
+        assert!(engine("guesswork").is_none());
+    }
+
+    #[test]
+    fn engines_agree() {
+        let board = Board::parse(TWO_SOLUTIONS).unwrap();
+        let mut all_solutions = engines().into_iter().map(|engine| {
+            let mut solutions = engine.solutions(&board).collect::<Vec<_>>();
+            solutions.sort_by_key(|solution| solution.to_string());
+            solutions
+        });
+
+        let first = all_solutions.next().unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(all_solutions.all(|solutions| solutions == first));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{unit::peers, Bitfield, Board, Digit};
+/// A plain backtracking solver. It fills the empty cells one at a time in reading order, trying
+/// each digit that doesn't clash with its row, column or box, and backs up whenever it reaches a
+/// cell that no digit fits.
+///
+/// This is the simplest possible strategy, and mostly exists as a baseline to compare the other
+/// solvers against. `SolverBackend` provides `solve` and `count_solutions` on top of `solutions`.
+pub struct Backtracker {
+    /// For each cell in reading order, every other cell that shares a row, column or box with it.
+    peers: Vec<Bitfield>,
+}
+
+impl Backtracker {
+    /// Create a new solver.
+    pub fn new() -> Self {
+        let peers = (0..81).map(|index| peers(index / 9, index % 9)).collect();
+
+        Backtracker { peers }
+    }
+
+    /// Lazily iterate through every solution to the given board.
+    pub fn solutions(&self, board: &Board) -> BacktrackSolutions<'_> {
+        let empty = (!board.filled())
+            .cells()
+            .map(|(row, col)| 9 * row + col)
+            .collect();
+
+        // The search never checks the clues against each other, so catch any clashes up front
+        let consistent = Digit::iter().all(|digit| {
+            board[digit]
+                .cells()
+                .all(|(row, col)| (board[digit] & self.peers[9 * row + col]).is_empty())
+        });
+
+        BacktrackSolutions {
+            peers: &self.peers,
+            board: board.clone(),
+            empty,
+            stack: Vec::new(),
+            next_digit: 0,
+            finished: !consistent,
+        }
+    }
+}
+
+impl Default for Backtracker {
+    fn default() -> Self {
+        Backtracker::new()
+    }
+}
+
+/// A lazy iterator through every solution to a board. Created by `Backtracker::solutions`.
+pub struct BacktrackSolutions<'a> {
+    peers: &'a [Bitfield],
+    /// The clues, plus every digit placed by the search so far.
+    board: Board,
+    /// The cells that the search has to fill, in the order it fills them.
+    empty: Vec<usize>,
+    /// The digit placed in each of the `empty` cells filled so far.
+    stack: Vec<Digit>,
+    /// How many digits to skip when trying to fill the next cell, because they have already been
+    /// tried.
+    next_digit: usize,
+    finished: bool,
+}
+
+impl BacktrackSolutions<'_> {
+    /// Remove the most recently placed digit, and arrange for the search to carry on from the
+    /// digit after it.
+    fn backtrack(&mut self) {
+        match self.stack.pop() {
+            Some(digit) => {
+                let index = self.empty[self.stack.len()];
+                self.board[digit] &= !Bitfield::new(index / 9, index % 9);
+                self.next_digit = Digit::iter().position(|other| other == digit).unwrap() + 1;
+            }
+            None => self.finished = true,
+        }
+    }
+}
+
+impl Iterator for BacktrackSolutions<'_> {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let level = self.stack.len();
+            let Some(&index) = self.empty.get(level) else {
+                let solution = self.board.clone();
+                self.backtrack();
+                return Some(solution);
+            };
+
+            let peers = self.peers[index];
+            let board = &self.board;
+            let found = Digit::iter()
+                .skip(self.next_digit)
+                .find(|&digit| (board[digit] & peers).is_empty());
+
+            match found {
+                Some(digit) => {
+                    self.board[digit] |= Bitfield::new(index / 9, index % 9);
+                    self.stack.push(digit);
+                    self.next_digit = 0;
+                }
+                None => self.backtrack(),
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Backtracker;
+    use crate::{
+        backend::SolverBackend,
+        solver::{Solver, TWO_SOLUTIONS},
+        Board,
+    };
+
+    #[test]
+    fn agrees_with_template_solver() {
+        let board = Board::parse(
+            "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
+        )
+        .unwrap();
+        assert_eq!(
+            Backtracker::new().solve(&board),
+            Solver::new().solve(&board)
+        );
+    }
+
+    #[test]
+    fn finds_every_solution() {
+        let board = Board::parse(TWO_SOLUTIONS).unwrap();
+        assert_eq!(Backtracker::new().count_solutions(&board, 10), 2);
+    }
+
+    #[test]
+    fn reports_conflicting_clues() {
+        let board = Board::parse(
+            "1.......1........................................................................",
+        )
+        .unwrap();
+        assert_eq!(Backtracker::new().count_solutions(&board, 2), 0);
+    }
+}
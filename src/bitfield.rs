@@ -23,6 +23,12 @@ impl Bitfield {
         Bitfield(1 << bit)
     }
 
+    /// Create a bitfield directly from its underlying bits. Only used for tables that are
+    /// generated at build time, which are already known to be in range.
+    pub(crate) const fn from_bits(bits: u128) -> Self {
+        Bitfield(bits)
+    }
+
     /// Is this bitfield completely empty? A bitfield which satisfies this can be created with
     /// `Default::default()`.
     pub fn is_empty(self) -> bool {
@@ -38,6 +44,20 @@ impl Bitfield {
     pub fn len(self) -> u32 {
         self.0.count_ones()
     }
+
+    /// Iterate through the `(row, col)` position of every true value within this bitfield, in
+    /// reading order.
+    pub fn cells(self) -> impl Iterator<Item = (usize, usize)> {
+        let mut remaining = self.0;
+        std::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            let bit = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            Some((bit / 9, bit % 9))
+        })
+    }
 }
 
 // Print the thing as an ascii-art board, using "!" to show where the bitfield is set.
@@ -166,6 +186,15 @@ mod test {
         assert_eq!(biggest.len(), 3);
     }
 
+    #[test]
+    fn cells_in_reading_order() {
+        let bitfield = Bitfield::new(3, 6) | Bitfield::new(1, 2) | Bitfield::new(8, 8);
+        let cells = bitfield.cells().collect::<Vec<_>>();
+
+        assert_eq!(cells, vec![(1, 2), (3, 6), (8, 8)]);
+        assert_eq!(Bitfield::default().cells().count(), 0);
+    }
+
     #[test]
     fn debug_format() {
         let bitfield = Bitfield::new(3, 6) | Bitfield::new(4, 5);
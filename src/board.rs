@@ -1,7 +1,10 @@
-use crate::{Bitfield, Digit};
+use crate::{
+    solver::{Solver, Uniqueness},
+    Bitfield, Digit,
+};
 use std::{
     fmt::{Display, Formatter, Write},
-    ops::{Index, IndexMut},
+    ops::{BitOr, Index, IndexMut},
 };
 
 /// A representation of a Sudoku. Rather than letting you look up what digit is located at a
@@ -17,7 +20,7 @@ pub struct Board {
 
 impl Board {
     /// Create an empty Sudoku board.
-    fn empty() -> Self {
+    pub(crate) fn empty() -> Self {
         Board {
             placements: [Default::default(); 9],
         }
@@ -76,6 +79,42 @@ impl Board {
             None
         }
     }
+
+    /// Every cell that has a digit in it.
+    pub fn filled(&self) -> Bitfield {
+        Digit::iter()
+            .map(|digit| self[digit])
+            .fold(Bitfield::default(), BitOr::bitor)
+    }
+
+    /// Find every cell whose contents differ between this board and the other board. A cell that
+    /// is empty in one board and filled in the other counts as a difference.
+    pub fn differences(&self, other: &Board) -> Bitfield {
+        Digit::iter()
+            .map(|digit| (self[digit] & !other[digit]) | (other[digit] & !self[digit]))
+            .fold(Bitfield::default(), BitOr::bitor)
+    }
+
+    /// Check whether this board has exactly one solution. When it has several, two distinct
+    /// solutions are returned along with the cells where they differ, which shows where another
+    /// clue is needed.
+    pub fn is_unique(&self) -> Uniqueness {
+        Solver::new().uniqueness(self)
+    }
+
+    /// Check whether this board has exactly one solution, and loses that if any one of its clues is
+    /// taken away.
+    pub fn is_minimal(&self) -> bool {
+        let solver = Solver::new();
+        solver.count_solutions(self, 2) == 1
+            && self.filled().cells().all(|(row, col)| {
+                let mut fewer = self.clone();
+                for digit in Digit::iter() {
+                    fewer[digit] &= !Bitfield::new(row, col);
+                }
+                solver.count_solutions(&fewer, 2) > 1
+            })
+    }
 }
 
 impl Index<Digit> for Board {
@@ -134,6 +173,7 @@ impl Display for Board {
 mod test {
     use super::Board;
     use crate::digit::Digit;
+    use crate::solver::{Uniqueness, TWO_SOLUTIONS};
     use crate::Bitfield;
 
     #[test]
@@ -224,4 +264,85 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn differences_between_boards() {
+        let first = Board::parse(
+            "12...............................................................................",
+        )
+        .unwrap();
+        let second = Board::parse(
+            "1.3..............................................................................",
+        )
+        .unwrap();
+
+        assert_eq!(first.differences(&first), Bitfield::default());
+        assert_eq!(
+            first.differences(&second),
+            Bitfield::new(0, 1) | Bitfield::new(0, 2)
+        );
+    }
+
+    #[test]
+    fn unique_board() {
+        let board = Board::parse(
+            "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
+        )
+        .unwrap();
+
+        assert!(board.is_unique().is_unique());
+    }
+
+    #[test]
+    fn ambiguous_board() {
+        let board = Board::parse(TWO_SOLUTIONS).unwrap();
+
+        match board.is_unique() {
+            Uniqueness::Multiple {
+                first,
+                second,
+                differences,
+            } => {
+                assert_ne!(first, second);
+                assert_eq!(
+                    differences,
+                    Bitfield::new(0, 1)
+                        | Bitfield::new(0, 8)
+                        | Bitfield::new(1, 1)
+                        | Bitfield::new(1, 8)
+                );
+            }
+            other => panic!("expected multiple solutions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unsolvable_board() {
+        let board = Board::parse(
+            ".23456789..................1.....................................................",
+        )
+        .unwrap();
+
+        assert_eq!(board.is_unique(), Uniqueness::Unsolvable);
+    }
+
+    #[test]
+    fn minimal_boards() {
+        let minimal = Board::parse(
+            "..6.......74..51.61...4.......9...8....25..74..8..4.2..5..9.2...6.413...........7",
+        )
+        .unwrap();
+        assert!(minimal.is_minimal());
+
+        // The same puzzle with an extra clue from its solution
+        let mut extra = minimal.clone();
+        extra[Digit::_9] |= Bitfield::new(0, 0);
+        assert!(extra.is_unique().is_unique());
+        assert!(!extra.is_minimal());
+
+        // Without its last clue, it has more than one solution
+        let mut fewer = minimal;
+        fewer[Digit::_7] &= !Bitfield::new(8, 8);
+        assert!(!fewer.is_minimal());
+    }
 }
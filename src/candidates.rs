@@ -0,0 +1,208 @@
+use crate::{
+    unit::{peers, Unit},
+    Bitfield, Board, Digit,
+};
+use std::{
+    fmt::{Display, Formatter, Write},
+    ops::{BitOr, Index, IndexMut},
+};
+
+/// The pencil marks for a Sudoku. Like `Board`, this is stored per digit: for each digit, there
+/// is a bitfield of every empty cell where that digit could still go. Cells that have already been
+/// filled have no candidates at all.
+///
+/// Use the `Index` and `IndexMut` traits, with `Digit` enums as lookups, to find the candidate
+/// cells for any given digit.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Candidates {
+    candidates: [Bitfield; 9],
+}
+
+impl Candidates {
+    /// Create an empty set of candidates, where no digit can go anywhere.
+    pub fn empty() -> Self {
+        Candidates {
+            candidates: [Default::default(); 9],
+        }
+    }
+
+    /// Work out the candidates for a board, by starting with every empty cell, and removing every
+    /// cell that shares a row, column or box with a placement of the same digit.
+    pub fn new(board: &Board) -> Self {
+        let filled = board.filled();
+        let mut output = Candidates::empty();
+        for digit in Digit::iter() {
+            let seen = Unit::iter()
+                .map(Unit::cells)
+                .filter(|&cells| !(cells & board[digit]).is_empty())
+                .fold(Bitfield::default(), BitOr::bitor);
+
+            output[digit] = !(filled | seen);
+        }
+
+        output
+    }
+
+    /// Every digit that could still go in the cell at `row`, `col`.
+    pub fn at(&self, row: usize, col: usize) -> impl Iterator<Item = Digit> + '_ {
+        let cell = Bitfield::new(row, col);
+        Digit::iter().filter(move |&digit| self[digit].contains(cell))
+    }
+
+    /// Every cell that has at least one candidate.
+    pub fn cells(&self) -> Bitfield {
+        self.candidates
+            .iter()
+            .cloned()
+            .fold(Bitfield::default(), BitOr::bitor)
+    }
+
+    /// Are there no candidates left at all?
+    pub fn is_empty(&self) -> bool {
+        self.candidates.iter().all(|cells| cells.is_empty())
+    }
+
+    /// How many candidates are there, in total, across every digit?
+    pub fn len(&self) -> u32 {
+        self.candidates.iter().map(|cells| cells.len()).sum()
+    }
+
+    /// Update the candidates to account for `digit` being placed at `row`, `col`. The cell loses
+    /// all of its candidates, and every peer of the cell loses `digit` as a candidate.
+    pub fn place(&mut self, row: usize, col: usize, digit: Digit) {
+        let cell = Bitfield::new(row, col);
+        for other in Digit::iter() {
+            self[other] &= !cell;
+        }
+        self[digit] &= !peers(row, col);
+    }
+
+    /// Remove every candidate which is set in `other`.
+    pub fn remove(&mut self, other: &Candidates) {
+        for digit in Digit::iter() {
+            self[digit] &= !other[digit];
+        }
+    }
+}
+
+impl Index<Digit> for Candidates {
+    type Output = Bitfield;
+
+    fn index(&self, index: Digit) -> &Self::Output {
+        let idx: usize = index.into();
+        &self.candidates[idx - 1]
+    }
+}
+
+impl IndexMut<Digit> for Candidates {
+    fn index_mut(&mut self, index: Digit) -> &mut Self::Output {
+        let idx: usize = index.into();
+        &mut self.candidates[idx - 1]
+    }
+}
+
+// Print the thing as a 27x27 grid of pencil marks. Each cell is drawn as a 3x3 block, with each
+// digit in the same position as it has on a phone keypad, or a space if it isn't a candidate.
+impl Display for Candidates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let row_sep = "+-----------+-+-----------+-+-----------+";
+        for line in 0..27 {
+            let row = line / 3;
+            let sub_row = line % 3;
+            if line % 9 == 0 {
+                f.write_str(row_sep)?;
+                writeln!(f)?;
+            }
+
+            f.write_char('|')?;
+            for col in 0..9 {
+                if col != 0 {
+                    if col % 3 == 0 {
+                        f.write_str("| |")?;
+                    } else {
+                        f.write_char(' ')?;
+                    }
+                }
+
+                let cell = Bitfield::new(row, col);
+                for digit in Digit::iter().skip(3 * sub_row).take(3) {
+                    if self[digit].contains(cell) {
+                        <Digit as Display>::fmt(&digit, f)?;
+                    } else {
+                        f.write_char(' ')?;
+                    }
+                }
+            }
+            f.write_char('|')?;
+            writeln!(f)?;
+        }
+
+        f.write_str(row_sep)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Candidates;
+    use crate::{Bitfield, Board, Digit};
+
+    const PUZZLE: &str =
+        "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3";
+
+    #[test]
+    fn filled_cells_have_no_candidates() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let candidates = Candidates::new(&board);
+
+        assert_eq!(candidates.at(0, 8).count(), 0);
+        assert_eq!(candidates.at(1, 2).count(), 0);
+    }
+
+    #[test]
+    fn eliminates_rows_columns_and_boxes() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let candidates = Candidates::new(&board);
+
+        // Row 0 has an 8, column 0 has a 4, and the top left box has a 3 and a 9
+        assert_eq!(
+            candidates.at(0, 0).collect::<Vec<_>>(),
+            vec![Digit::_1, Digit::_2, Digit::_5, Digit::_6, Digit::_7]
+        );
+        // No 8 can go anywhere in row 0 or column 8, or the top right box
+        assert!((candidates[Digit::_8] & Bitfield::new(0, 0)).is_empty());
+        assert!((candidates[Digit::_8] & Bitfield::new(5, 8)).is_empty());
+        assert!((candidates[Digit::_8] & Bitfield::new(2, 6)).is_empty());
+    }
+
+    #[test]
+    fn placing_updates_peers() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let mut candidates = Candidates::new(&board);
+        candidates.place(0, 0, Digit::_1);
+
+        assert_eq!(candidates.at(0, 0).count(), 0);
+        assert!(!candidates[Digit::_1].contains(Bitfield::new(0, 5)));
+        assert!(!candidates[Digit::_1].contains(Bitfield::new(6, 0)));
+        assert!(!candidates[Digit::_1].contains(Bitfield::new(2, 2)));
+    }
+
+    #[test]
+    fn display_format() {
+        let board = Board::parse(
+            "1234567.9........................................................................",
+        )
+        .unwrap();
+        let string = Candidates::new(&board).to_string();
+        let lines = string.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 31);
+        assert_eq!(lines[0], "+-----------+-+-----------+-+-----------+");
+        // Only an 8 can go into the gap in the first row
+        assert_eq!(lines[1], "|           | |           | |           |");
+        assert_eq!(lines[3], "|           | |           | |     8     |");
+        // The rest of the top left box can't hold a 1, 2 or 3
+        assert_eq!(lines[4], "|           | |123 123 123| |123 123 123|");
+    }
+}
@@ -49,9 +49,9 @@ impl Digit {
     }
 }
 
-impl Into<usize> for Digit {
-    fn into(self) -> usize {
-        match self {
+impl From<Digit> for usize {
+    fn from(digit: Digit) -> Self {
+        match digit {
             Digit::_1 => 1,
             Digit::_2 => 2,
             Digit::_3 => 3,
@@ -67,14 +67,14 @@ impl Into<usize> for Digit {
 
 impl Debug for Digit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let number: usize = self.clone().into();
+        let number: usize = (*self).into();
         <usize as Debug>::fmt(&number, f)
     }
 }
 
 impl Display for Digit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let number: usize = self.clone().into();
+        let number: usize = (*self).into();
         <usize as Display>::fmt(&number, f)
     }
 }
@@ -0,0 +1,361 @@
+use crate::{Bitfield, Board, Digit};
+
+/// The number of constraints in Sudoku as an exact cover problem. Every cell holds one digit, and
+/// every row, column and box holds each digit once, giving 4 groups of 81 constraints.
+const COLUMNS: usize = 324;
+
+/// The number of choices in Sudoku as an exact cover problem: one digit in one cell.
+const ROWS: usize = 729;
+
+/// A Sudoku solver using Knuth's Algorithm X with "dancing links". Sudoku is expressed as an exact
+/// cover problem, where each of the 729 possible placements of a digit in a cell covers 4 of the
+/// 324 constraints, and a solution is a set of placements that covers every constraint once.
+///
+/// This is completely independent of the path based `Solver`, so the two can be used to check one
+/// another. `SolverBackend` provides `solve` and `count_solutions` on top of `solutions`.
+#[derive(Default)]
+pub struct DlxSolver;
+
+impl DlxSolver {
+    /// Create a new solver.
+    pub fn new() -> Self {
+        DlxSolver
+    }
+
+    /// Lazily iterate through every solution to the given board.
+    pub fn solutions(&self, board: &Board) -> DlxSolutions {
+        let mut matrix = Matrix::new();
+        let mut consistent = true;
+
+        // Clues are placed up front by removing every constraint they cover, so the search only
+        // has to fill in the empty cells.
+        for (index, digit) in Digit::iter().enumerate() {
+            for (row, col) in board[digit].cells() {
+                consistent &= matrix.select(9 * (9 * row + col) + index);
+            }
+        }
+
+        DlxSolutions {
+            board: board.clone(),
+            matrix,
+            stack: Vec::new(),
+            state: if consistent {
+                State::Descending
+            } else {
+                State::Finished
+            },
+        }
+    }
+}
+
+/// The toroidal doubly linked lists that make up the exact cover matrix. Node 0 is the root, nodes
+/// 1 to 324 are the column headers, and every matrix row is then 4 consecutive nodes.
+struct Matrix {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node.
+    column: Vec<usize>,
+    /// The matrix row of each node, which is meaningless for the root and column headers.
+    row: Vec<usize>,
+    /// The number of nodes remaining in each column, indexed by column header.
+    size: Vec<usize>,
+}
+
+impl Matrix {
+    /// Build the full matrix for an empty Sudoku.
+    fn new() -> Self {
+        let nodes = 1 + COLUMNS + 4 * ROWS;
+        let mut matrix = Matrix {
+            left: Vec::with_capacity(nodes),
+            right: Vec::with_capacity(nodes),
+            up: Vec::with_capacity(nodes),
+            down: Vec::with_capacity(nodes),
+            column: Vec::with_capacity(nodes),
+            row: Vec::with_capacity(nodes),
+            size: vec![0; 1 + COLUMNS],
+        };
+
+        for header in 0..=COLUMNS {
+            matrix
+                .left
+                .push(if header == 0 { COLUMNS } else { header - 1 });
+            matrix
+                .right
+                .push(if header == COLUMNS { 0 } else { header + 1 });
+            matrix.up.push(header);
+            matrix.down.push(header);
+            matrix.column.push(header);
+            matrix.row.push(0);
+        }
+
+        for row in 0..ROWS {
+            let cell = row / 9;
+            let digit = row % 9;
+            let (cell_row, cell_col) = (cell / 9, cell % 9);
+            let square = 3 * (cell_row / 3) + cell_col / 3;
+            let columns = [
+                1 + cell,
+                1 + 81 + 9 * cell_row + digit,
+                1 + 162 + 9 * cell_col + digit,
+                1 + 243 + 9 * square + digit,
+            ];
+
+            let first = matrix.left.len();
+            for (offset, header) in columns.into_iter().enumerate() {
+                let node = first + offset;
+                matrix
+                    .left
+                    .push(if offset == 0 { first + 3 } else { node - 1 });
+                matrix
+                    .right
+                    .push(if offset == 3 { first } else { node + 1 });
+
+                let last = matrix.up[header];
+                matrix.up.push(last);
+                matrix.down.push(header);
+                matrix.down[last] = node;
+                matrix.up[header] = node;
+
+                matrix.column.push(header);
+                matrix.row.push(row);
+                matrix.size[header] += 1;
+            }
+        }
+
+        matrix
+    }
+
+    /// Remove a column from the header list, and remove every row that intersects it from the
+    /// other columns.
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// Exactly undo `cover`. Columns have to be uncovered in the opposite order to how they were
+    /// covered.
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// Cover every column of the row containing `node`, other than the column of `node` itself.
+    fn cover_row(&mut self, node: usize) {
+        let mut other = self.right[node];
+        while other != node {
+            self.cover(self.column[other]);
+            other = self.right[other];
+        }
+    }
+
+    /// Exactly undo `cover_row`.
+    fn uncover_row(&mut self, node: usize) {
+        let mut other = self.left[node];
+        while other != node {
+            self.uncover(self.column[other]);
+            other = self.left[other];
+        }
+    }
+
+    /// Commit to a matrix row before the search starts, by covering all of its columns. Returns
+    /// false if one of those columns has already been covered, in which case the row conflicts
+    /// with an earlier selection.
+    fn select(&mut self, row: usize) -> bool {
+        let first = 1 + COLUMNS + 4 * row;
+        let still_open = (first..first + 4).all(|node| {
+            let header = self.column[node];
+            self.left[self.right[header]] == header
+        });
+
+        if still_open {
+            self.cover(self.column[first]);
+            self.cover_row(first);
+        }
+
+        still_open
+    }
+
+    /// Choose the uncovered column with the fewest remaining rows.
+    fn smallest_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut header = self.right[0];
+        while header != 0 {
+            if best.is_none_or(|best| self.size[header] < self.size[best]) {
+                best = Some(header);
+            }
+            header = self.right[header];
+        }
+        best
+    }
+}
+
+/// Where the search is up to, between calls to `next`.
+enum State {
+    /// The last step chose a new row, so the search should go deeper.
+    Descending,
+    /// The last step hit a dead end, or found a solution, so the search should undo its most
+    /// recent choice and try the next option.
+    Backtracking,
+    /// Every option has been explored.
+    Finished,
+}
+
+/// A lazy iterator through every solution to a board. Created by `DlxSolver::solutions`.
+pub struct DlxSolutions {
+    board: Board,
+    matrix: Matrix,
+    /// The chosen node at each level of the search.
+    stack: Vec<usize>,
+    state: State,
+}
+
+impl DlxSolutions {
+    /// Build a board from the clues and the rows chosen so far.
+    fn solution(&self) -> Board {
+        let mut solution = self.board.clone();
+        for &node in &self.stack {
+            let row = self.matrix.row[node];
+            let cell = row / 9;
+            let digit = Digit::iter().nth(row % 9).unwrap();
+            solution[digit] |= Bitfield::new(cell / 9, cell % 9);
+        }
+        solution
+    }
+}
+
+impl Iterator for DlxSolutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.state {
+                State::Finished => return None,
+                State::Descending => {
+                    let Some(header) = self.matrix.smallest_column() else {
+                        // Every constraint is covered
+                        self.state = State::Backtracking;
+                        return Some(self.solution());
+                    };
+
+                    self.matrix.cover(header);
+                    let node = self.matrix.down[header];
+                    if node == header {
+                        self.matrix.uncover(header);
+                        self.state = State::Backtracking;
+                        continue;
+                    }
+
+                    self.matrix.cover_row(node);
+                    self.stack.push(node);
+                }
+                State::Backtracking => {
+                    let Some(node) = self.stack.pop() else {
+                        self.state = State::Finished;
+                        continue;
+                    };
+
+                    self.matrix.uncover_row(node);
+                    let header = self.matrix.column[node];
+                    let next = self.matrix.down[node];
+                    if next == header {
+                        self.matrix.uncover(header);
+                        continue;
+                    }
+
+                    self.matrix.cover_row(next);
+                    self.stack.push(next);
+                    self.state = State::Descending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DlxSolver;
+    use crate::{
+        backend::SolverBackend,
+        solver::{Solver, TWO_SOLUTIONS},
+        Board,
+    };
+
+    const PUZZLES: [&str; 3] = [
+        "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
+        "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+    ];
+
+    #[test]
+    fn agrees_with_template_solver() {
+        let dlx = DlxSolver::new();
+        let templates = Solver::new();
+        for puzzle in PUZZLES {
+            let board = Board::parse(puzzle).unwrap();
+            assert_eq!(dlx.solve(&board), templates.solve(&board));
+        }
+    }
+
+    #[test]
+    fn finds_every_solution() {
+        let board = Board::parse(TWO_SOLUTIONS).unwrap();
+        let mut dlx = DlxSolver::new().solutions(&board).collect::<Vec<_>>();
+        let mut templates = Solver::new().solutions(&board).collect::<Vec<_>>();
+        dlx.sort_by_key(|board| board.to_string());
+        templates.sort_by_key(|board| board.to_string());
+
+        assert_eq!(dlx.len(), 2);
+        assert_eq!(dlx, templates);
+    }
+
+    #[test]
+    fn count_stops_at_limit() {
+        let board = Board::parse(&".".repeat(81)).unwrap();
+        assert_eq!(DlxSolver::new().count_solutions(&board, 5), 5);
+    }
+
+    #[test]
+    fn reports_unsolvable_board() {
+        // The top left cell must be a 1, but there is already a 1 further down its column
+        let board = Board::parse(
+            ".23456789..................1.....................................................",
+        )
+        .unwrap();
+        assert_eq!(DlxSolver::new().count_solutions(&board, 2), 0);
+    }
+
+    #[test]
+    fn reports_conflicting_clues() {
+        let board = Board::parse(
+            "1.......1........................................................................",
+        )
+        .unwrap();
+        assert_eq!(DlxSolver::new().count_solutions(&board, 2), 0);
+    }
+}
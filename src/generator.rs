@@ -0,0 +1,356 @@
+use crate::{
+    logic::{rate_with, Rating, Technique},
+    rng::Rng,
+    solver::Solver,
+    Bitfield, Board, Digit, PathDb,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+/// The reasons that a puzzle can't be generated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GenerateError {
+    /// Every attempt made a puzzle that was outside the difficulty band, or needed a technique
+    /// that wasn't allowed. `discarded` is the number of puzzles that were thrown away.
+    OutOfAttempts { discarded: usize },
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::OutOfAttempts { discarded } => write!(
+                f,
+                "no puzzle of the right difficulty was found after discarding {} puzzles",
+                discarded
+            ),
+        }
+    }
+}
+
+impl Error for GenerateError {}
+
+/// A way of laying out clues so that the pattern looks the same after it has been transformed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    /// Clues can go anywhere.
+    None,
+    /// The pattern looks the same after turning the board half way around.
+    Rotational180,
+    /// The pattern looks the same after turning the board a quarter of the way around.
+    Rotational90,
+    /// The pattern looks the same after reflecting it along the diagonal from the top left to the
+    /// bottom right.
+    Diagonal,
+    /// The pattern looks the same after reflecting it from left to right.
+    Mirror,
+}
+
+impl Symmetry {
+    /// Every cell that the cell at `row`, `col` is mapped to by the symmetry, including itself.
+    /// A symmetric pattern has either all of these cells, or none of them.
+    pub fn orbit(self, row: usize, col: usize) -> Bitfield {
+        let images = match self {
+            Symmetry::None => vec![(row, col)],
+            Symmetry::Rotational180 => vec![(row, col), (8 - row, 8 - col)],
+            Symmetry::Rotational90 => vec![
+                (row, col),
+                (col, 8 - row),
+                (8 - row, 8 - col),
+                (8 - col, row),
+            ],
+            Symmetry::Diagonal => vec![(row, col), (col, row)],
+            Symmetry::Mirror => vec![(row, col), (row, 8 - col)],
+        };
+
+        images
+            .into_iter()
+            .fold(Bitfield::default(), |cells, (row, col)| {
+                cells | Bitfield::new(row, col)
+            })
+    }
+
+    /// Does the pattern look the same after being transformed?
+    pub fn is_symmetric(self, cells: Bitfield) -> bool {
+        cells
+            .cells()
+            .all(|(row, col)| cells.contains(self.orbit(row, col)))
+    }
+}
+
+/// Generate a random puzzle with a unique solution. The same seed always gives the same puzzle.
+///
+/// A random full grid is built first, and then clues are removed one at a time, in a random
+/// order, as long as the puzzle still has a unique solution. Every clue left at the end is needed.
+pub fn generate(seed: u64) -> Board {
+    generate_with_symmetry(seed, Symmetry::None)
+}
+
+/// Generate a random puzzle with a unique solution, where the clues are laid out with the given
+/// symmetry. The same seed and symmetry always give the same puzzle.
+///
+/// This works like `generate`, but removes every clue in an orbit of the symmetry at once, so some
+/// clues may be left that would not be needed on their own.
+pub fn generate_with_symmetry(seed: u64, symmetry: Symmetry) -> Board {
+    let mut rng = Rng::new(seed);
+    let grid = full_grid(&mut rng);
+    remove_clues(&mut rng, &grid, symmetry)
+}
+
+/// A generated puzzle, along with how it was rated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rated {
+    pub puzzle: Board,
+    pub rating: Rating,
+    /// The number of puzzles that were generated and thrown away before this one was found.
+    pub discarded: usize,
+}
+
+/// Generate puzzles until one can be solved using only the allowed `techniques`, with a rating
+/// inside `band`. Gives up after `attempts` puzzles. The same arguments always give the same
+/// puzzle.
+pub fn generate_rated(
+    seed: u64,
+    band: RangeInclusive<f32>,
+    techniques: impl IntoIterator<Item = Technique>,
+    attempts: usize,
+) -> Result<Rated, GenerateError> {
+    let techniques = techniques.into_iter().collect::<Vec<_>>();
+    let mut rng = Rng::new(seed);
+    for discarded in 0..attempts {
+        let puzzle = generate(rng.next_u64());
+        let rating = rate_with(&puzzle, techniques.iter().cloned())
+            .expect("generated puzzles have a unique solution");
+        if rating.solved && band.contains(&rating.score) {
+            return Ok(Rated {
+                puzzle,
+                rating,
+                discarded,
+            });
+        }
+    }
+
+    Err(GenerateError::OutOfAttempts {
+        discarded: attempts,
+    })
+}
+
+/// Build a random, completely filled grid, by picking a random path for each digit that doesn't
+/// overlap with the paths already picked.
+pub(crate) fn full_grid(rng: &mut Rng) -> Board {
+    let paths = PathDb::shared().paths();
+    let unassigned = Digit::iter()
+        .map(|digit| (digit, paths.to_vec()))
+        .collect::<Vec<_>>();
+
+    let mut grid = Board::empty();
+    for (digit, path) in pick_paths(rng, unassigned).expect("an empty grid can always be filled") {
+        grid[digit] = path;
+    }
+    grid
+}
+
+/// Assign each digit one of its paths, without any overlapping, by trying the paths of the most
+/// constrained digit in a random order.
+fn pick_paths(
+    rng: &mut Rng,
+    mut unassigned: Vec<(Digit, Vec<Bitfield>)>,
+) -> Option<Vec<(Digit, Bitfield)>> {
+    let Some((most_constrained, _)) = unassigned
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (_, paths))| paths.len())
+    else {
+        return Some(Vec::new());
+    };
+    let (digit, mut paths) = unassigned.swap_remove(most_constrained);
+    rng.shuffle(&mut paths);
+
+    for path in paths {
+        let rest = unassigned
+            .iter()
+            .map(|(other, others)| {
+                let others = others
+                    .iter()
+                    .cloned()
+                    .filter(|&other| (other & path).is_empty())
+                    .collect::<Vec<_>>();
+                (*other, others)
+            })
+            .collect::<Vec<_>>();
+        if rest.iter().any(|(_, paths)| paths.is_empty()) {
+            continue;
+        }
+
+        if let Some(mut assigned) = pick_paths(rng, rest) {
+            assigned.push((digit, path));
+            return Some(assigned);
+        }
+    }
+
+    None
+}
+
+/// Take clues away from a board in a random order, a whole orbit of the symmetry at a time,
+/// skipping any orbit whose removal would leave the board with more than one solution.
+fn remove_clues(rng: &mut Rng, board: &Board, symmetry: Symmetry) -> Board {
+    let mut orbits = Vec::new();
+    let mut covered = Bitfield::default();
+    for (row, col) in board.filled().cells() {
+        if !covered.contains(Bitfield::new(row, col)) {
+            let orbit = symmetry.orbit(row, col);
+            orbits.push(orbit);
+            covered |= orbit;
+        }
+    }
+    rng.shuffle(&mut orbits);
+    remove_orbits(board, orbits)
+}
+
+/// Take away every clue that isn't needed for the board to have a unique solution, trying the
+/// clues in reading order. The result is always minimal, and solves to the same grid. A board
+/// without a unique solution is returned unchanged, since taking clues away can't fix that.
+pub fn minimize(board: &Board) -> Board {
+    let clues = board
+        .filled()
+        .cells()
+        .map(|(row, col)| Bitfield::new(row, col))
+        .collect();
+    remove_orbits(board, clues)
+}
+
+/// Take away each group of clues in turn, skipping any group whose removal would leave the board
+/// with more than one solution.
+fn remove_orbits(board: &Board, orbits: Vec<Bitfield>) -> Board {
+    let solver = Solver::new();
+    let mut puzzle = board.clone();
+    for orbit in orbits {
+        let mut fewer = puzzle.clone();
+        for digit in Digit::iter() {
+            fewer[digit] &= !orbit;
+        }
+
+        if solver.count_solutions(&fewer, 2) == 1 {
+            puzzle = fewer;
+        }
+    }
+    puzzle
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        full_grid, generate, generate_rated, generate_with_symmetry, minimize, GenerateError,
+        Symmetry,
+    };
+    use crate::{all_paths, logic::Technique, rng::Rng, solver::Solver, Bitfield, Board, Digit};
+
+    #[test]
+    fn full_grid_is_valid() {
+        let grid = full_grid(&mut Rng::new(1));
+
+        let mut total = Bitfield::default();
+        for digit in Digit::iter() {
+            assert!(all_paths().contains(&grid[digit]));
+            total |= grid[digit];
+        }
+        assert!((!total).is_empty());
+    }
+
+    #[test]
+    fn puzzle_is_unique() {
+        let puzzle = generate(1);
+        assert_eq!(Solver::new().count_solutions(&puzzle, 2), 1);
+    }
+
+    #[test]
+    fn every_clue_is_needed() {
+        assert!(generate(2).is_minimal());
+    }
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(3), generate(3));
+        assert_ne!(generate(3), generate(4));
+    }
+
+    #[test]
+    fn orbits() {
+        assert_eq!(
+            Symmetry::Rotational180.orbit(0, 1),
+            Bitfield::new(0, 1) | Bitfield::new(8, 7)
+        );
+        assert_eq!(
+            Symmetry::Rotational90.orbit(0, 1),
+            Bitfield::new(0, 1) | Bitfield::new(1, 8) | Bitfield::new(8, 7) | Bitfield::new(7, 0)
+        );
+        assert_eq!(
+            Symmetry::Diagonal.orbit(0, 1),
+            Bitfield::new(0, 1) | Bitfield::new(1, 0)
+        );
+        assert_eq!(
+            Symmetry::Mirror.orbit(0, 1),
+            Bitfield::new(0, 1) | Bitfield::new(0, 7)
+        );
+        assert_eq!(Symmetry::Rotational90.orbit(4, 4), Bitfield::new(4, 4));
+    }
+
+    #[test]
+    fn clues_are_symmetric() {
+        let symmetries = [
+            Symmetry::Rotational180,
+            Symmetry::Rotational90,
+            Symmetry::Diagonal,
+            Symmetry::Mirror,
+        ];
+        for (seed, symmetry) in symmetries.into_iter().enumerate() {
+            let puzzle = generate_with_symmetry(seed as u64, symmetry);
+            assert!(symmetry.is_symmetric(puzzle.filled()), "{:?}", symmetry);
+            assert_eq!(Solver::new().count_solutions(&puzzle, 2), 1);
+        }
+    }
+
+    #[test]
+    fn rated_puzzle_is_in_band() {
+        let singles = [Technique::HiddenSingle, Technique::NakedSingle];
+        let rated = generate_rated(1, 2.0..=2.5, singles, 50).unwrap();
+
+        assert!(rated.rating.solved);
+        assert_eq!(rated.rating.hardest, Some(Technique::NakedSingle));
+        assert_eq!(generate_rated(1, 2.0..=2.5, singles, 50), Ok(rated));
+    }
+
+    #[test]
+    fn gives_up_after_attempts() {
+        let easy = Technique::iter().filter(|technique| technique.difficulty() < 3.0);
+        assert_eq!(
+            generate_rated(1, 9.0..=10.0, easy, 3),
+            Err(GenerateError::OutOfAttempts { discarded: 3 })
+        );
+    }
+
+    #[test]
+    fn minimizes_puzzle() {
+        let board = Board::parse(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        assert!(!board.is_minimal());
+
+        let minimal = minimize(&board);
+        assert!(minimal.is_minimal());
+        assert!(board.filled().contains(minimal.filled()));
+        assert_eq!(Solver::new().solve(&minimal), Solver::new().solve(&board));
+    }
+
+    #[test]
+    fn minimize_leaves_multiple_solutions_alone() {
+        let board = Board::parse(
+            "12345678.........................................................................",
+        )
+        .unwrap();
+        assert_eq!(minimize(&board), board);
+    }
+}
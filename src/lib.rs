@@ -1,9 +1,23 @@
+pub mod backend;
+pub mod backtrack;
 mod bitfield;
 mod board;
+mod candidates;
 mod digit;
+pub mod dlx;
+pub mod generator;
+pub mod logic;
 mod path;
+mod path_db;
+mod rng;
+pub mod sampler;
+pub mod solver;
+mod unit;
 
 pub use bitfield::Bitfield;
 pub use board::Board;
+pub use candidates::Candidates;
 pub use digit::Digit;
-pub use path::generate_paths;
\ No newline at end of file
+pub use path::{all_paths, generate_paths};
+pub use path_db::PathDb;
+pub use unit::{peers, Unit};
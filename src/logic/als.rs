@@ -0,0 +1,270 @@
+use super::{combinations, Deduction, Pattern, Step, Technique};
+use crate::{peers, Bitfield, Candidates, Digit, Unit};
+
+/// An almost locked set: `N` empty cells in one unit which, between them, can only hold `N + 1`
+/// digits. If any one of those digits is ruled out, the rest have to fill the cells.
+struct Als {
+    unit: Unit,
+    cells: Bitfield,
+    /// The digits the cells can hold, as a mask with bit `n` set for digit `n + 1`.
+    digits: u16,
+    /// For each digit, the cells in the set that could hold it.
+    by_digit: [Bitfield; 9],
+    /// For each digit, every cell that sees all of the set's cells which could hold it.
+    seen: [Bitfield; 9],
+}
+
+impl Als {
+    fn new(candidates: &Candidates, unit: Unit, cells: Bitfield) -> Self {
+        let mut als = Als {
+            unit,
+            cells,
+            digits: 0,
+            by_digit: [Bitfield::default(); 9],
+            seen: [!Bitfield::default(); 9],
+        };
+
+        for (index, digit) in Digit::iter().enumerate() {
+            let within = candidates[digit] & cells;
+            if within.is_empty() {
+                continue;
+            }
+
+            als.digits |= 1 << index;
+            als.by_digit[index] = within;
+            for (row, col) in within.cells() {
+                als.seen[index] &= peers(row, col);
+            }
+        }
+
+        als
+    }
+
+    /// The digits that the cells can hold, in order.
+    fn digits(&self) -> impl Iterator<Item = (usize, Digit)> + '_ {
+        Digit::iter()
+            .enumerate()
+            .filter(move |(index, _)| self.digits & (1 << index) != 0)
+    }
+
+    /// The digits which can't be in both sets at once, because every cell in one set that could
+    /// hold the digit sees every cell in the other set that could hold it.
+    fn restricted(&self, other: &Als) -> Vec<(usize, Digit)> {
+        if !(self.cells & other.cells).is_empty() {
+            return Vec::new();
+        }
+
+        self.digits()
+            .filter(|&(index, _)| {
+                other.digits & (1 << index) != 0 && self.seen[index].contains(other.by_digit[index])
+            })
+            .collect()
+    }
+}
+
+/// Every almost locked set in every unit. A set that lies in more than one unit is only listed
+/// once.
+fn almost_locked_sets(candidates: &Candidates) -> Vec<Als> {
+    let mut output: Vec<Als> = Vec::new();
+    for unit in Unit::iter() {
+        let empty = (candidates.cells() & unit.cells())
+            .cells()
+            .map(|(row, col)| Bitfield::new(row, col))
+            .collect::<Vec<_>>();
+
+        for size in 1..=empty.len() {
+            for subset in combinations(&empty, size) {
+                let cells = subset
+                    .into_iter()
+                    .fold(Bitfield::default(), |cells, cell| cells | cell);
+                let als = Als::new(candidates, unit, cells);
+                if als.digits.count_ones() as usize == size + 1
+                    && output.iter().all(|other| other.cells != cells)
+                {
+                    output.push(als);
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Remove every digit other than those in `restricted`, which is common to both `first` and
+/// `second`, from the cells that see every copy of it in both.
+fn common_eliminations(
+    candidates: &Candidates,
+    first: &Als,
+    second: &Als,
+    restricted: &[Digit],
+) -> Candidates {
+    let mut removed = Candidates::empty();
+    for (index, digit) in first.digits() {
+        if restricted.contains(&digit) || second.digits & (1 << index) == 0 {
+            continue;
+        }
+
+        removed[digit] = candidates[digit] & first.seen[index] & second.seen[index];
+    }
+    removed
+}
+
+fn als_step(
+    technique: Technique,
+    sets: &[&Als],
+    restricted: Vec<Digit>,
+    removed: Candidates,
+) -> Option<Step> {
+    if removed.is_empty() {
+        return None;
+    }
+
+    let digits = sets.iter().fold(0, |digits, als| digits | als.digits);
+    Some(Step {
+        technique,
+        deduction: Deduction::Elimination(removed),
+        units: sets.iter().map(|als| als.unit).collect(),
+        cells: sets
+            .iter()
+            .fold(Bitfield::default(), |cells, als| cells | als.cells),
+        digits: Digit::iter()
+            .enumerate()
+            .filter(|(index, _)| digits & (1 << index) != 0)
+            .map(|(_, digit)| digit)
+            .collect(),
+        pattern: Pattern::Als {
+            sets: sets.iter().map(|als| als.cells).collect(),
+            restricted,
+        },
+    })
+}
+
+/// Find two almost locked sets which share a restricted common digit X. Only one of the sets can
+/// hold X, so the other is locked, and any other digit Z they have in common must be in one of
+/// them. Z can be removed from every cell that sees all of the Zs in both sets.
+pub(super) fn als_xz(candidates: &Candidates) -> Option<Step> {
+    let sets = almost_locked_sets(candidates);
+    for (index, first) in sets.iter().enumerate() {
+        for second in &sets[index + 1..] {
+            for (_, x) in first.restricted(second) {
+                let removed = common_eliminations(candidates, first, second, &[x]);
+                let step = als_step(Technique::AlsXz, &[first, second], vec![x], removed);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Find an almost locked set C, which has a restricted common digit X with another set A, and a
+/// different restricted common digit Y with a third set B. C can't lose both X and Y, so either A
+/// or B is locked, and any digit Z that A and B have in common can be removed from every cell that
+/// sees all of the Zs in both.
+pub(super) fn als_xy_wing(candidates: &Candidates) -> Option<Step> {
+    let sets = almost_locked_sets(candidates);
+    let links = sets
+        .iter()
+        .map(|pivot| {
+            sets.iter()
+                .enumerate()
+                .flat_map(|(index, other)| {
+                    pivot
+                        .restricted(other)
+                        .into_iter()
+                        .map(move |(_, digit)| (index, digit))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for (pivot, links) in sets.iter().zip(&links) {
+        for (position, &(first, x)) in links.iter().enumerate() {
+            for &(second, y) in &links[position + 1..] {
+                let (first, second) = (&sets[first], &sets[second]);
+                if x == y || !(first.cells & second.cells).is_empty() {
+                    continue;
+                }
+
+                let removed = common_eliminations(candidates, first, second, &[x, y]);
+                let step = als_step(
+                    Technique::AlsXyWing,
+                    &[first, second, pivot],
+                    vec![x, y],
+                    removed,
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{almost_locked_sets, als_xy_wing, als_xz};
+    use crate::{
+        logic::{from_cells, Deduction, Pattern, Technique},
+        Bitfield, Candidates, Digit,
+    };
+
+    #[test]
+    fn lists_each_set_once() {
+        use Digit::*;
+        let candidates = from_cells(&[((0, 0), &[_1, _2]), ((0, 1), &[_1, _3])]);
+        let sets = almost_locked_sets(&candidates);
+
+        // Both cells on their own, and the two together, each of which is in a row and a box
+        assert_eq!(sets.len(), 3);
+    }
+
+    #[test]
+    fn finds_als_xz() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((4, 0), &[_2, _3]),
+            ((4, 4), &[_1, _3]),
+            ((0, 4), &[_1, _5]),
+        ]);
+        let step = als_xz(&candidates).unwrap();
+
+        // Either r1c1 is a 1, or the 2 is missing from row 5, and r5c5 is a 1
+        let mut eliminations = Candidates::empty();
+        eliminations[_1] = Bitfield::new(0, 4);
+        assert_eq!(step.technique, Technique::AlsXz);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Als {
+                sets: vec![
+                    Bitfield::new(0, 0),
+                    Bitfield::new(4, 0) | Bitfield::new(4, 4)
+                ],
+                restricted: vec![_2],
+            }
+        );
+    }
+
+    #[test]
+    fn finds_als_xy_wing() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 4), &[_1, _3, _6]),
+            ((1, 4), &[_1, _6]),
+            ((4, 4), &[_1, _2]),
+            ((4, 0), &[_2, _3]),
+            ((0, 0), &[_3, _5]),
+        ]);
+        let step = als_xy_wing(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[_3] = Bitfield::new(0, 0);
+        assert_eq!(step.technique, Technique::AlsXyWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+    }
+}
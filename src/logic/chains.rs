@@ -0,0 +1,324 @@
+use super::{Deduction, Pattern, Step, Technique};
+use crate::{peers, Bitfield, Candidates, Digit, Unit};
+use std::collections::VecDeque;
+
+/// A single candidate, numbered as `9 * cell + digit`, where cells are numbered in reading order
+/// and digits from 0.
+type Node = usize;
+
+fn node(row: usize, col: usize, digit: Digit) -> Node {
+    9 * (9 * row + col) + usize::from(digit) - 1
+}
+
+fn position(node: Node) -> (usize, usize) {
+    (node / 81, node / 9 % 9)
+}
+
+fn digit(node: Node) -> Digit {
+    Digit::iter().nth(node % 9).unwrap()
+}
+
+/// Every link between candidates that a technique is allowed to use. A strong link means at least
+/// one of the two candidates is true, and a weak link means at most one of them is.
+struct Graph {
+    strong: Vec<Vec<Node>>,
+    weak: Vec<Vec<Node>>,
+}
+
+impl Graph {
+    /// Find the links for a technique. X-Chains only link candidates for the same digit within a
+    /// unit, XY-Chains only use strong links inside cells with two candidates, and weak links
+    /// between cells, and AICs can use anything.
+    fn new(candidates: &Candidates, technique: Technique) -> Self {
+        let mut graph = Graph {
+            strong: vec![Vec::new(); 729],
+            weak: vec![Vec::new(); 729],
+        };
+
+        for digit in Digit::iter() {
+            for (row, col) in candidates[digit].cells() {
+                let from = node(row, col, digit);
+                let cell = Bitfield::new(row, col);
+
+                if technique != Technique::XYChain {
+                    for unit in Unit::containing(row, col) {
+                        let others = candidates[digit] & unit.cells() & !cell;
+                        if others.len() == 1 {
+                            let (other_row, other_col) = others.cells().next().unwrap();
+                            graph.strong[from].push(node(other_row, other_col, digit));
+                        }
+                    }
+                }
+
+                for (other_row, other_col) in (candidates[digit] & peers(row, col)).cells() {
+                    graph.weak[from].push(node(other_row, other_col, digit));
+                }
+
+                if technique != Technique::XChain {
+                    let others = candidates
+                        .at(row, col)
+                        .filter(|&other| other != digit)
+                        .collect::<Vec<_>>();
+                    if let [other] = others[..] {
+                        graph.strong[from].push(node(row, col, other));
+                    }
+                    if technique == Technique::Aic {
+                        for other in others {
+                            graph.weak[from].push(node(row, col, other));
+                        }
+                    }
+                }
+
+                graph.strong[from].sort_unstable();
+                graph.strong[from].dedup();
+            }
+        }
+
+        graph
+    }
+
+    /// Search outwards from `start` for the shortest chain that removes a candidate, and which
+    /// has fewer than `limit` candidates in it. Chains always begin and end with a strong link,
+    /// and have at least two of them.
+    fn shortest(
+        &self,
+        candidates: &Candidates,
+        technique: Technique,
+        start: Node,
+        limit: usize,
+    ) -> Option<(Vec<Node>, Candidates)> {
+        // Each state is a candidate, along with whether the next link has to be strong
+        let mut parent = vec![None; 2 * 729];
+        let mut seen = vec![false; 2 * 729];
+        let mut queue = VecDeque::new();
+        seen[2 * start + 1] = true;
+        queue.push_back((2 * start + 1, 1));
+
+        while let Some((state, length)) = queue.pop_front() {
+            if length + 1 >= limit {
+                break;
+            }
+
+            let strong = state % 2 == 1;
+            let links = if strong {
+                &self.strong[state / 2]
+            } else {
+                &self.weak[state / 2]
+            };
+
+            for &next in links {
+                let next_state = 2 * next + usize::from(!strong);
+                if seen[next_state] {
+                    continue;
+                }
+                seen[next_state] = true;
+                parent[next_state] = Some(state);
+
+                if strong && length + 1 >= 4 {
+                    let mut chain = vec![next];
+                    let mut current = next_state;
+                    while let Some(previous) = parent[current] {
+                        chain.push(previous / 2);
+                        current = previous;
+                    }
+                    chain.reverse();
+
+                    let mut distinct = chain.clone();
+                    distinct.sort_unstable();
+                    distinct.dedup();
+                    if distinct.len() == chain.len() {
+                        if let Some(removed) = conclusion(candidates, technique, start, next) {
+                            return Some((chain, removed));
+                        }
+                    }
+                }
+
+                queue.push_back((next_state, length + 1));
+            }
+        }
+
+        None
+    }
+}
+
+/// Work out what can be removed when at least one of `first` and `last` must be true. If they
+/// are the same digit, it can be removed from every cell that sees both. Otherwise, for an AIC
+/// only, if they share a cell then every other digit can be removed from it, and if they see one
+/// another then each digit can be removed from the other's cell.
+fn conclusion(
+    candidates: &Candidates,
+    technique: Technique,
+    first: Node,
+    last: Node,
+) -> Option<Candidates> {
+    let (first_row, first_col) = position(first);
+    let (last_row, last_col) = position(last);
+    let first_cell = Bitfield::new(first_row, first_col);
+    let last_cell = Bitfield::new(last_row, last_col);
+    let (x, y) = (digit(first), digit(last));
+
+    let mut removed = Candidates::empty();
+    if x == y {
+        removed[x] = candidates[x] & peers(first_row, first_col) & peers(last_row, last_col);
+    } else if technique == Technique::Aic {
+        if first_cell == last_cell {
+            for other in candidates.at(first_row, first_col) {
+                if other != x && other != y {
+                    removed[other] = first_cell;
+                }
+            }
+        } else if peers(first_row, first_col).contains(last_cell) {
+            removed[y] = candidates[y] & first_cell;
+            removed[x] = candidates[x] & last_cell;
+        }
+    }
+
+    if removed.is_empty() {
+        None
+    } else {
+        Some(removed)
+    }
+}
+
+/// Find the shortest chain of alternating strong and weak links, of the kind that `technique`
+/// allows. Either the first or last candidate in the chain is true, since if the first is false
+/// then the strong link makes the second true, the weak link makes the third false, and so on.
+fn search(candidates: &Candidates, technique: Technique) -> Option<Step> {
+    let graph = Graph::new(candidates, technique);
+    let mut best: Option<(Vec<Node>, Candidates)> = None;
+    for start in 0..729 {
+        if graph.strong[start].is_empty() {
+            continue;
+        }
+
+        let limit = best.as_ref().map_or(usize::MAX, |(chain, _)| chain.len());
+        if let Some(found) = graph.shortest(candidates, technique, start, limit) {
+            best = Some(found);
+        }
+    }
+
+    let (chain, removed) = best?;
+    let mut digits = chain.iter().map(|&node| digit(node)).collect::<Vec<_>>();
+    digits.sort();
+    digits.dedup();
+
+    Some(Step {
+        technique,
+        deduction: Deduction::Elimination(removed),
+        units: Vec::new(),
+        cells: chain
+            .iter()
+            .map(|&node| Bitfield::new(position(node).0, position(node).1))
+            .fold(Bitfield::default(), |cells, cell| cells | cell),
+        digits,
+        pattern: Pattern::Chain(
+            chain
+                .into_iter()
+                .map(|node| (position(node), digit(node)))
+                .collect(),
+        ),
+    })
+}
+
+/// Find a chain for a single digit, where the strong links are units with only two places for
+/// the digit.
+pub(super) fn x_chain(candidates: &Candidates) -> Option<Step> {
+    search(candidates, Technique::XChain)
+}
+
+/// Find a chain of cells which can each only hold two digits, where each cell shares a digit with
+/// the next, and the first and last cells share a digit.
+pub(super) fn xy_chain(candidates: &Candidates) -> Option<Step> {
+    search(candidates, Technique::XYChain)
+}
+
+/// Find an alternating inference chain, which can mix links within units and within cells, and
+/// can end on different digits.
+pub(super) fn aic(candidates: &Candidates) -> Option<Step> {
+    search(candidates, Technique::Aic)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{aic, x_chain, xy_chain};
+    use crate::{
+        logic::{Deduction, Pattern, Technique},
+        Bitfield, Candidates, Digit,
+    };
+
+    #[test]
+    fn finds_x_chain() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_5] = Bitfield::new(0, 1)
+            | Bitfield::new(0, 7)
+            | Bitfield::new(4, 7)
+            | Bitfield::new(4, 2)
+            | Bitfield::new(1, 2)
+            | Bitfield::new(2, 0)
+            | Bitfield::new(7, 2);
+        let step = x_chain(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_5] = Bitfield::new(1, 2);
+        assert_eq!(step.technique, Technique::XChain);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Chain(vec![
+                ((0, 1), Digit::_5),
+                ((0, 7), Digit::_5),
+                ((4, 7), Digit::_5),
+                ((4, 2), Digit::_5),
+            ])
+        );
+    }
+
+    #[test]
+    fn finds_xy_chain() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_1] =
+            Bitfield::new(0, 0) | Bitfield::new(4, 8) | Bitfield::new(0, 8) | Bitfield::new(4, 0);
+        candidates[Digit::_2] = Bitfield::new(0, 0) | Bitfield::new(0, 4);
+        candidates[Digit::_3] = Bitfield::new(0, 4) | Bitfield::new(4, 4);
+        candidates[Digit::_4] = Bitfield::new(4, 4) | Bitfield::new(4, 8);
+        candidates[Digit::_5] = Bitfield::new(0, 8) | Bitfield::new(4, 0);
+        candidates[Digit::_6] = Bitfield::new(0, 8) | Bitfield::new(4, 0);
+        assert!(x_chain(&candidates).is_none());
+        let step = xy_chain(&candidates).unwrap();
+
+        // The chain runs 1-2, 2-3, 3-4 and 4-1, so one of its ends must be a 1
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_1] = Bitfield::new(0, 8) | Bitfield::new(4, 0);
+        assert_eq!(step.technique, Technique::XYChain);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.cells,
+            Bitfield::new(0, 0) | Bitfield::new(0, 4) | Bitfield::new(4, 4) | Bitfield::new(4, 8)
+        );
+        assert_eq!(
+            step.digits,
+            vec![Digit::_1, Digit::_2, Digit::_3, Digit::_4]
+        );
+    }
+
+    #[test]
+    fn finds_aic_with_different_ends() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_5] = Bitfield::new(0, 0) | Bitfield::new(0, 4);
+        candidates[Digit::_8] = Bitfield::new(0, 0) | Bitfield::new(0, 4) | Bitfield::new(0, 5);
+        candidates[Digit::_9] = Bitfield::new(0, 0) | Bitfield::new(0, 4) | Bitfield::new(0, 5);
+        assert!(x_chain(&candidates).is_none());
+        assert!(xy_chain(&candidates).is_none());
+        let step = aic(&candidates).unwrap();
+
+        // Either the top left cell is a 5, or the 8 in box 2 is in the top right of the pair
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_8] = Bitfield::new(0, 0);
+        assert_eq!(step.technique, Technique::Aic);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.to_string(),
+            "AIC: r1c1<>8 via (5)r1c1=(5)r1c5-(8)r1c5=(8)r1c6"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use super::{combinations, Deduction, Pattern, Step, Technique};
+use crate::{Bitfield, Candidates, Digit, Unit};
+use std::ops::BitOr;
+
+/// The technique for a fish of the given size and kind.
+fn technique(size: usize, finned: bool, sashimi: bool) -> Technique {
+    match (size, finned, sashimi) {
+        (2, false, _) => Technique::XWing,
+        (3, false, _) => Technique::Swordfish,
+        (4, false, _) => Technique::Jellyfish,
+        (2, true, false) => Technique::FinnedXWing,
+        (3, true, false) => Technique::FinnedSwordfish,
+        (4, true, false) => Technique::FinnedJellyfish,
+        (2, true, true) => Technique::SashimiXWing,
+        (3, true, true) => Technique::SashimiSwordfish,
+        (4, true, true) => Technique::SashimiJellyfish,
+        _ => panic!("no technique for a fish of size {}", size),
+    }
+}
+
+/// The two ways a fish can lie: with rows as the base and columns as the cover, or the other way
+/// around.
+fn orientations() -> [(Vec<Unit>, Vec<Unit>); 2] {
+    let rows = (0..9).map(Unit::Row).collect::<Vec<_>>();
+    let cols = (0..9).map(Unit::Col).collect::<Vec<_>>();
+    [(rows.clone(), cols.clone()), (cols, rows)]
+}
+
+/// Every cell in any of the units.
+fn union(units: &[Unit]) -> Bitfield {
+    units
+        .iter()
+        .map(|unit| unit.cells())
+        .fold(Bitfield::default(), BitOr::bitor)
+}
+
+/// Look at every way of picking `size` base lines for each digit, calling `check` with the digit,
+/// the base lines, the lines in the other direction that have candidates in the base, and those
+/// candidates.
+fn search(
+    candidates: &Candidates,
+    size: usize,
+    mut check: impl FnMut(Digit, &[Unit], Vec<Unit>, Bitfield) -> Option<Step>,
+) -> Option<Step> {
+    for digit in Digit::iter() {
+        for (bases, covers) in orientations() {
+            let bases = bases
+                .into_iter()
+                .filter(|base| !(candidates[digit] & base.cells()).is_empty())
+                .collect::<Vec<_>>();
+
+            for base in combinations(&bases, size) {
+                let cells = candidates[digit] & union(&base);
+                let touched = covers
+                    .iter()
+                    .cloned()
+                    .filter(|cover| !(cells & cover.cells()).is_empty())
+                    .collect::<Vec<_>>();
+
+                if let Some(step) = check(digit, &base, touched, cells) {
+                    return Some(step);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Find `size` rows where a digit can only go in the same `size` columns, or the other way around.
+/// Each of those columns must have its copy of the digit in one of the rows, so the digit can be
+/// removed from the rest of the columns.
+pub(super) fn basic(candidates: &Candidates, size: usize) -> Option<Step> {
+    search(candidates, size, |digit, base, cover, cells| {
+        if cover.len() != size {
+            return None;
+        }
+
+        let removed = candidates[digit] & union(&cover) & !union(base);
+        if removed.is_empty() {
+            return None;
+        }
+
+        let mut eliminations = Candidates::empty();
+        eliminations[digit] = removed;
+        Some(Step {
+            technique: technique(size, false, false),
+            deduction: Deduction::Elimination(eliminations),
+            units: base.iter().chain(&cover).cloned().collect(),
+            cells,
+            digits: vec![digit],
+            pattern: Pattern::Fish {
+                base: base.to_vec(),
+                cover,
+                fins: Bitfield::default(),
+            },
+        })
+    })
+}
+
+/// Find a fish with some extra candidates, the fins, that all lie in one box. Either one of the
+/// fins is true, or the fish is, and both rule out the cells of the cover lines inside the fins'
+/// box. A sashimi fish is one where some base line only has a single cell outside the fins, so
+/// would not be a fish at all without them.
+pub(super) fn finned(candidates: &Candidates, size: usize, sashimi: bool) -> Option<Step> {
+    search(candidates, size, |digit, base, touched, cells| {
+        if touched.len() <= size {
+            return None;
+        }
+
+        combinations(&touched, size).into_iter().find_map(|cover| {
+            let covered = union(&cover);
+            let fins = cells & !covered;
+            let square = (0..9)
+                .map(Unit::Box)
+                .find(|square| square.cells().contains(fins))?;
+
+            let body = base
+                .iter()
+                .map(|line| (cells & line.cells() & covered).len())
+                .collect::<Vec<_>>();
+            if body.contains(&0) || body.contains(&1) != sashimi {
+                return None;
+            }
+
+            let removed = candidates[digit] & covered & square.cells() & !union(base);
+            if removed.is_empty() {
+                return None;
+            }
+
+            let mut eliminations = Candidates::empty();
+            eliminations[digit] = removed;
+            Some(Step {
+                technique: technique(size, true, sashimi),
+                deduction: Deduction::Elimination(eliminations),
+                units: base.iter().chain(&cover).cloned().collect(),
+                cells,
+                digits: vec![digit],
+                pattern: Pattern::Fish {
+                    base: base.to_vec(),
+                    cover,
+                    fins,
+                },
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{basic, finned};
+    use crate::{
+        logic::{Deduction, Pattern, Technique},
+        Bitfield, Candidates, Digit, Unit,
+    };
+
+    #[test]
+    fn finds_x_wing() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_5] = Bitfield::new(1, 2)
+            | Bitfield::new(1, 7)
+            | Bitfield::new(6, 2)
+            | Bitfield::new(6, 7)
+            | Bitfield::new(3, 2)
+            | Bitfield::new(3, 4)
+            | Bitfield::new(8, 7)
+            | Bitfield::new(8, 0);
+        let step = basic(&candidates, 2).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_5] = Bitfield::new(3, 2) | Bitfield::new(8, 7);
+        assert_eq!(step.technique, Technique::XWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Fish {
+                base: vec![Unit::Row(1), Unit::Row(6)],
+                cover: vec![Unit::Col(2), Unit::Col(7)],
+                fins: Bitfield::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn finds_swordfish_in_columns() {
+        // Columns 0, 4 and 8 only have 3s in rows 2, 5 and 7, with no more than two per column
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_3] = Bitfield::new(2, 0)
+            | Bitfield::new(5, 0)
+            | Bitfield::new(5, 4)
+            | Bitfield::new(7, 4)
+            | Bitfield::new(2, 8)
+            | Bitfield::new(7, 8)
+            | Bitfield::new(2, 3)
+            | Bitfield::new(7, 1)
+            | Bitfield::new(0, 3)
+            | Bitfield::new(0, 1)
+            | Bitfield::new(4, 3)
+            | Bitfield::new(4, 6);
+        assert!(basic(&candidates, 2).is_none());
+        let step = basic(&candidates, 3).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_3] = Bitfield::new(2, 3) | Bitfield::new(7, 1);
+        assert_eq!(step.technique, Technique::Swordfish);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.units,
+            vec![
+                Unit::Col(0),
+                Unit::Col(4),
+                Unit::Col(8),
+                Unit::Row(2),
+                Unit::Row(5),
+                Unit::Row(7)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_finned_x_wing() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_5] = Bitfield::new(0, 0)
+            | Bitfield::new(0, 4)
+            | Bitfield::new(0, 7)
+            | Bitfield::new(1, 2)
+            | Bitfield::new(1, 7)
+            | Bitfield::new(1, 8)
+            | Bitfield::new(2, 0)
+            | Bitfield::new(2, 3)
+            | Bitfield::new(2, 5)
+            | Bitfield::new(3, 1)
+            | Bitfield::new(3, 4)
+            | Bitfield::new(3, 8)
+            | Bitfield::new(4, 1)
+            | Bitfield::new(4, 3)
+            | Bitfield::new(4, 5)
+            | Bitfield::new(4, 6)
+            | Bitfield::new(5, 0)
+            | Bitfield::new(5, 4)
+            | Bitfield::new(5, 6)
+            | Bitfield::new(6, 2)
+            | Bitfield::new(6, 7)
+            | Bitfield::new(7, 1)
+            | Bitfield::new(7, 3)
+            | Bitfield::new(7, 6)
+            | Bitfield::new(7, 8)
+            | Bitfield::new(8, 0)
+            | Bitfield::new(8, 4)
+            | Bitfield::new(8, 5);
+        assert!(basic(&candidates, 2).is_none());
+        assert!(finned(&candidates, 2, true).is_none());
+        let step = finned(&candidates, 2, false).unwrap();
+
+        // Only the cell that shares a box with the fin can be removed
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_5] = Bitfield::new(0, 7);
+        assert_eq!(step.technique, Technique::FinnedXWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Fish {
+                base: vec![Unit::Row(1), Unit::Row(6)],
+                cover: vec![Unit::Col(2), Unit::Col(7)],
+                fins: Bitfield::new(1, 8),
+            }
+        );
+    }
+
+    #[test]
+    fn finds_sashimi_x_wing() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_5] = Bitfield::new(0, 0)
+            | Bitfield::new(0, 4)
+            | Bitfield::new(0, 7)
+            | Bitfield::new(1, 2)
+            | Bitfield::new(1, 6)
+            | Bitfield::new(1, 8)
+            | Bitfield::new(2, 0)
+            | Bitfield::new(2, 3)
+            | Bitfield::new(2, 5)
+            | Bitfield::new(3, 1)
+            | Bitfield::new(3, 4)
+            | Bitfield::new(3, 8)
+            | Bitfield::new(4, 1)
+            | Bitfield::new(4, 3)
+            | Bitfield::new(4, 5)
+            | Bitfield::new(4, 6)
+            | Bitfield::new(5, 0)
+            | Bitfield::new(5, 4)
+            | Bitfield::new(5, 6)
+            | Bitfield::new(6, 2)
+            | Bitfield::new(6, 7)
+            | Bitfield::new(7, 1)
+            | Bitfield::new(7, 3)
+            | Bitfield::new(7, 6)
+            | Bitfield::new(7, 8)
+            | Bitfield::new(8, 0)
+            | Bitfield::new(8, 4)
+            | Bitfield::new(8, 5);
+        assert!(finned(&candidates, 2, false).is_none());
+        let step = finned(&candidates, 2, true).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_5] = Bitfield::new(0, 7);
+        assert_eq!(step.technique, Technique::SashimiXWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Fish {
+                base: vec![Unit::Row(1), Unit::Row(6)],
+                cover: vec![Unit::Col(2), Unit::Col(7)],
+                fins: Bitfield::new(1, 6) | Bitfield::new(1, 8),
+            }
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use super::{Step, Technique};
+use crate::{solver::Uniqueness, Board, Candidates, Digit, Unit};
+
+/// How much a hint gives away, from least to most.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Level {
+    /// Only which technique to look for.
+    Technique,
+    /// The technique, and where to look for it.
+    Unit,
+    /// Exactly what can be deduced.
+    Answer,
+}
+
+/// Something a player has got wrong.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Mistake {
+    /// `digit` has been placed at `cell`, but it might not go there.
+    Placement { cell: (usize, usize), digit: Digit },
+    /// `digit` has been removed from the pencil marks at `cell`, but it belongs there.
+    Elimination { cell: (usize, usize), digit: Digit },
+}
+
+impl Mistake {
+    fn cell(&self) -> (usize, usize) {
+        match *self {
+            Mistake::Placement { cell, .. } | Mistake::Elimination { cell, .. } => cell,
+        }
+    }
+}
+
+/// Help for a player who is stuck.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Hint {
+    /// The player has made mistakes, which need fixing before they can go any further.
+    Mistakes(Vec<Mistake>),
+    /// The easiest deduction that can be made next.
+    Step(Box<Step>),
+}
+
+impl Hint {
+    /// Describe the hint for a player, giving away as much as `level` allows. Cells are written as
+    /// "r1c1", numbered from 1.
+    pub fn reveal(&self, level: Level) -> String {
+        match self {
+            Hint::Mistakes(mistakes) => match (level, &mistakes[..]) {
+                (_, []) => String::from("There are no mistakes on the board"),
+                (Level::Technique, [_]) => String::from("There is a mistake on the board"),
+                (Level::Technique, _) => {
+                    format!("There are {} mistakes on the board", mistakes.len())
+                }
+                (Level::Unit, [first, ..]) => {
+                    let (row, col) = first.cell();
+                    format!("There is a mistake in {}", Unit::containing(row, col)[2])
+                }
+                (Level::Answer, _) => mistakes
+                    .iter()
+                    .map(|mistake| match *mistake {
+                        Mistake::Placement {
+                            cell: (row, col),
+                            digit,
+                        } => format!("r{}c{} is not {}", row + 1, col + 1, digit),
+                        Mistake::Elimination {
+                            cell: (row, col),
+                            digit,
+                        } => format!("r{}c{} can be {}", row + 1, col + 1, digit),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            },
+            Hint::Step(step) => match level {
+                Level::Technique => format!("Look for a {}", step.technique),
+                Level::Unit if step.units.is_empty() => {
+                    let cells = step
+                        .cells
+                        .cells()
+                        .map(|(row, col)| format!("r{}c{}", row + 1, col + 1))
+                        .collect::<Vec<_>>();
+                    format!("Look for a {} using {}", step.technique, cells.join(", "))
+                }
+                Level::Unit => {
+                    let units = step.units.iter().map(Unit::to_string).collect::<Vec<_>>();
+                    format!("Look for a {} in {}", step.technique, units.join(", "))
+                }
+                Level::Answer => step.to_string(),
+            },
+        }
+    }
+}
+
+/// Compare a player's placements and pencil marks with the solution. Placements that differ from
+/// it are wrong, as are pencil marks that leave out its digit in a cell that has been marked.
+fn mistakes(board: &Board, candidates: &Candidates, solution: &Board) -> Vec<Mistake> {
+    let marked = candidates.cells() & !board.filled();
+    let mut mistakes = Vec::new();
+    for digit in Digit::iter() {
+        for cell in (board[digit] & !solution[digit]).cells() {
+            mistakes.push(Mistake::Placement { cell, digit });
+        }
+        for cell in (solution[digit] & marked & !candidates[digit]).cells() {
+            mistakes.push(Mistake::Elimination { cell, digit });
+        }
+    }
+    mistakes.sort_by_key(Mistake::cell);
+    mistakes
+}
+
+/// Find the next hint for a player, given the puzzle's clues, the board with their placements so
+/// far, and their pencil marks. Any empty cell without pencil marks is treated as if every digit
+/// that fits were marked.
+///
+/// Mistakes come first: placements and pencil marks that don't agree with the puzzle's solution.
+/// Otherwise, the hint is the easiest step that the logical solver can find. Returns None if the
+/// clues don't have exactly one solution, the board is already solved, or the logical solver is
+/// stuck.
+///
+/// The clues are needed as well as the board: a board on its own doesn't say which placements
+/// were given and which were made by the player, so a wrong placement can't be told apart from a
+/// clue, and the clues can be blamed for the player's mistakes.
+pub fn hint(puzzle: &Board, board: &Board, candidates: &Candidates) -> Option<Hint> {
+    let Uniqueness::Unique(solution) = puzzle.is_unique() else {
+        return None;
+    };
+
+    let mistakes = mistakes(board, candidates, &solution);
+    if !mistakes.is_empty() {
+        return Some(Hint::Mistakes(mistakes));
+    }
+
+    let mut marks = Candidates::new(board);
+    let unmarked = !candidates.cells();
+    for digit in Digit::iter() {
+        marks[digit] &= candidates[digit] | unmarked;
+    }
+
+    Technique::iter()
+        .find_map(|technique| technique.find(board, &marks))
+        .map(|step| Hint::Step(Box::new(step)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hint, Hint, Level, Mistake};
+    use crate::{
+        logic::{Deduction, Technique},
+        Bitfield, Board, Candidates, Digit, Unit,
+    };
+
+    const PUZZLE: &str =
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..";
+
+    #[test]
+    fn hints_at_easiest_step() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let Some(Hint::Step(step)) = hint(&board, &board, &Candidates::empty()) else {
+            panic!("expected a step");
+        };
+
+        assert_eq!(step.technique, Technique::HiddenSingle);
+    }
+
+    #[test]
+    fn reveals_more_at_each_level() {
+        let board = Board::parse(
+            "53467891.672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        let hint = hint(&board, &board, &Candidates::empty()).unwrap();
+
+        assert_eq!(hint.reveal(Level::Technique), "Look for a Hidden Single");
+        assert_eq!(
+            hint.reveal(Level::Unit),
+            "Look for a Hidden Single in row 1"
+        );
+        assert_eq!(hint.reveal(Level::Answer), "Hidden Single: r1c9=2 in row 1");
+    }
+
+    #[test]
+    fn uses_pencil_marks() {
+        // The player has ruled out every 9 in the top row apart from the one in r1c1, which is
+        // a hidden single that can't be seen without the pencil marks
+        let board = Board::parse(
+            "..6.......74..51.61...4.......9...8....25..74..8..4.2..5..9.2...6.413...........7",
+        )
+        .unwrap();
+        let without = hint(&board, &board, &Candidates::empty()).unwrap();
+
+        let mut candidates = Candidates::new(&board);
+        candidates[Digit::_9] &= !Unit::Row(0).cells() | Bitfield::new(0, 0);
+        let Some(Hint::Step(step)) = hint(&board, &board, &candidates) else {
+            panic!("expected a step");
+        };
+
+        assert_eq!(step.technique, Technique::HiddenSingle);
+        assert_eq!(
+            step.deduction,
+            Deduction::Placement {
+                cell: (0, 0),
+                digit: Digit::_9
+            }
+        );
+        assert_ne!(Hint::Step(step), without);
+    }
+
+    #[test]
+    fn reports_wrong_placements() {
+        // The top left cell should be a 4, and the bottom right a 2. The clues are all right, so
+        // none of them are blamed.
+        let puzzle = Board::parse(PUZZLE).unwrap();
+        let mut board = puzzle.clone();
+        board[Digit::_5] |= Bitfield::new(0, 0);
+        board[Digit::_4] |= Bitfield::new(8, 8);
+        let hint = hint(&puzzle, &board, &Candidates::empty()).unwrap();
+
+        assert_eq!(
+            hint,
+            Hint::Mistakes(vec![
+                Mistake::Placement {
+                    cell: (0, 0),
+                    digit: Digit::_5
+                },
+                Mistake::Placement {
+                    cell: (8, 8),
+                    digit: Digit::_4
+                }
+            ])
+        );
+        assert_eq!(
+            hint.reveal(Level::Technique),
+            "There are 2 mistakes on the board"
+        );
+        assert_eq!(hint.reveal(Level::Unit), "There is a mistake in box 1");
+        assert_eq!(hint.reveal(Level::Answer), "r1c1 is not 5, r9c9 is not 4");
+    }
+
+    #[test]
+    fn reports_wrong_elimination() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let mut candidates = Candidates::new(&board);
+        candidates[Digit::_4] &= !Bitfield::new(0, 0);
+        let hint = hint(&board, &board, &candidates).unwrap();
+
+        assert_eq!(
+            hint,
+            Hint::Mistakes(vec![Mistake::Elimination {
+                cell: (0, 0),
+                digit: Digit::_4
+            }])
+        );
+        assert_eq!(hint.reveal(Level::Answer), "r1c1 can be 4");
+    }
+
+    #[test]
+    fn no_hint_for_solved_board() {
+        let board = Board::parse(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        assert_eq!(hint(&board, &board, &Candidates::empty()), None);
+    }
+
+    #[test]
+    fn no_hint_without_unique_solution() {
+        let puzzle = Board::parse(
+            "12345678.........................................................................",
+        )
+        .unwrap();
+        assert_eq!(hint(&puzzle, &puzzle, &Candidates::empty()), None);
+    }
+}
@@ -0,0 +1,117 @@
+use super::{Deduction, Pattern, Step, Technique};
+use crate::{Candidates, Digit, Unit};
+
+/// Every pair of a box, and a row or column passing through it.
+fn intersections() -> impl Iterator<Item = (Unit, Unit)> {
+    (0..9).flat_map(|square| {
+        let rows = (0..3).map(move |offset| Unit::Row(3 * (square / 3) + offset));
+        let cols = (0..3).map(move |offset| Unit::Col(3 * (square % 3) + offset));
+        rows.chain(cols).map(move |line| (Unit::Box(square), line))
+    })
+}
+
+/// Look for a digit whose candidates in `first` all lie inside `second`, so that it can be removed
+/// from the rest of `second`.
+fn locked(
+    candidates: &Candidates,
+    technique: Technique,
+    first: Unit,
+    second: Unit,
+) -> Option<Step> {
+    Digit::iter().find_map(|digit| {
+        let cells = candidates[digit] & first.cells();
+        if cells.is_empty() || !second.cells().contains(cells) {
+            return None;
+        }
+
+        let removed = candidates[digit] & second.cells() & !first.cells();
+        if removed.is_empty() {
+            return None;
+        }
+
+        let mut eliminations = Candidates::empty();
+        eliminations[digit] = removed;
+        Some(Step {
+            technique,
+            deduction: Deduction::Elimination(eliminations),
+            units: vec![first, second],
+            cells,
+            digits: vec![digit],
+            pattern: Pattern::Basic,
+        })
+    })
+}
+
+/// Find a box where a digit can only go in one row or column, which rules that digit out of the
+/// rest of the row or column.
+pub(super) fn pointing(candidates: &Candidates) -> Option<Step> {
+    intersections().find_map(|(square, line)| locked(candidates, Technique::Pointing, square, line))
+}
+
+/// Find a row or column where a digit can only go in one box, which rules that digit out of the
+/// rest of the box.
+pub(super) fn box_line_reduction(candidates: &Candidates) -> Option<Step> {
+    intersections()
+        .find_map(|(square, line)| locked(candidates, Technique::BoxLineReduction, line, square))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{box_line_reduction, pointing};
+    use crate::{
+        logic::{Deduction, Technique},
+        Bitfield, Candidates, Digit, Unit,
+    };
+
+    #[test]
+    fn finds_pointing_pair() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_4] = Bitfield::new(0, 0)
+            | Bitfield::new(0, 2)
+            | Bitfield::new(0, 5)
+            | Bitfield::new(0, 7)
+            | Bitfield::new(4, 5);
+        let step = pointing(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_4] = Bitfield::new(0, 5) | Bitfield::new(0, 7);
+        assert_eq!(step.technique, Technique::Pointing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.units, vec![Unit::Box(0), Unit::Row(0)]);
+        assert_eq!(step.cells, Bitfield::new(0, 0) | Bitfield::new(0, 2));
+        assert_eq!(
+            step.to_string(),
+            "Pointing: r1c6<>4, r1c8<>4 in box 1, row 1"
+        );
+    }
+
+    #[test]
+    fn finds_box_line_reduction() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_7] = Bitfield::new(3, 0)
+            | Bitfield::new(3, 4)
+            | Bitfield::new(4, 0)
+            | Bitfield::new(4, 3)
+            | Bitfield::new(4, 5)
+            | Bitfield::new(5, 4)
+            | Bitfield::new(5, 8)
+            | Bitfield::new(8, 3)
+            | Bitfield::new(8, 5);
+        // Column 4 only has candidates in the centre box
+        let step = box_line_reduction(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_7] = Bitfield::new(4, 3) | Bitfield::new(4, 5);
+        assert_eq!(step.technique, Technique::BoxLineReduction);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.units, vec![Unit::Col(4), Unit::Box(4)]);
+    }
+
+    #[test]
+    fn nothing_to_remove() {
+        let mut candidates = Candidates::empty();
+        candidates[Digit::_2] = Bitfield::new(0, 0) | Bitfield::new(4, 4) | Bitfield::new(8, 8);
+        assert!(pointing(&candidates).is_none());
+        assert!(box_line_reduction(&candidates).is_none());
+    }
+}
@@ -0,0 +1,622 @@
+//! A solver which works the way a person would, by repeatedly looking for a pattern in the pencil
+//! marks that justifies placing a digit or removing a candidate, rather than by searching.
+
+mod als;
+mod chains;
+mod fish;
+mod hint;
+mod intersections;
+mod rating;
+mod singles;
+mod subsets;
+mod templates;
+mod uniqueness;
+mod wings;
+
+pub use hint::{hint, Hint, Level, Mistake};
+pub use rating::{rate, rate_with, Rating};
+
+use crate::{Bitfield, Board, Candidates, Digit, Unit};
+use std::fmt::{Display, Formatter};
+
+/// A named pattern that a person can look for to make progress on a puzzle. Techniques are listed
+/// from easiest to hardest.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Technique {
+    /// The only cell in a unit that can hold a digit.
+    HiddenSingle,
+    /// The only digit that can go in a cell.
+    NakedSingle,
+    /// A digit confined to one line within a box, so it can't go elsewhere in that line.
+    Pointing,
+    /// A digit confined to one box within a line, so it can't go elsewhere in that box.
+    BoxLineReduction,
+    /// Two cells in a unit that can only hold the same two digits.
+    NakedPair,
+    /// A digit confined to the same two columns within two rows, or the other way around.
+    XWing,
+    /// Two digits that can only go in the same two cells of a unit.
+    HiddenPair,
+    /// Three cells in a unit that can only hold the same three digits.
+    NakedTriple,
+    /// A digit confined to the same three columns within three rows, or the other way around.
+    Swordfish,
+    /// Three digits that can only go in the same three cells of a unit.
+    HiddenTriple,
+    /// A cell with two candidates, seeing two others that each share one of its digits and a third
+    /// digit.
+    XYWing,
+    /// Like an XY-Wing, but the middle cell can also hold the third digit.
+    XYZWing,
+    /// Two cells with the same two candidates, joined by a unit where one of those digits can only
+    /// go in two places.
+    WWing,
+    /// A rectangle of cells that would be deadly, if not for the extra digits in one corner.
+    UniqueRectangle1,
+    /// A rectangle with the same single extra digit in two corners on one side.
+    UniqueRectangle2,
+    /// A rectangle whose extra digits form a naked subset with other cells.
+    UniqueRectangle3,
+    /// A rectangle where one of its digits is locked into the two corners with extra digits.
+    UniqueRectangle4,
+    /// A rectangle with the same single extra digit in two opposite corners, or three corners.
+    UniqueRectangle5,
+    /// A rectangle where one of its digits forms an X-Wing on the corners.
+    UniqueRectangle6,
+    /// An X-Wing with extra candidates, all in one box.
+    FinnedXWing,
+    /// A finned X-Wing which would be missing a corner without its fins.
+    SashimiXWing,
+    /// Four cells in a unit that can only hold the same four digits.
+    NakedQuad,
+    /// A digit confined to the same four columns within four rows, or the other way around.
+    Jellyfish,
+    /// Four digits that can only go in the same four cells of a unit.
+    HiddenQuad,
+    /// A single cell with three candidates, which would otherwise leave the puzzle without a unique
+    /// solution.
+    BugPlusOne,
+    /// A Swordfish with extra candidates, all in one box.
+    FinnedSwordfish,
+    /// A finned Swordfish which would be missing a corner without its fins.
+    SashimiSwordfish,
+    /// A Jellyfish with extra candidates, all in one box.
+    FinnedJellyfish,
+    /// A finned Jellyfish which would be missing a corner without its fins.
+    SashimiJellyfish,
+    /// A chain of strong and weak links between the candidates for one digit.
+    XChain,
+    /// A chain of cells with two candidates each, where each cell shares a digit with the next.
+    XYChain,
+    /// An alternating inference chain, mixing links between cells and links within cells.
+    Aic,
+    /// Two almost locked sets which can't both hold the same digit.
+    AlsXz,
+    /// Three almost locked sets, where one is linked to each of the other two by a different
+    /// digit.
+    AlsXyWing,
+    /// A digit's candidates compared against every template it could still take.
+    Template,
+    /// Templates, keeping only those that fit alongside some template of every other digit.
+    TemplateCombination,
+}
+
+impl Technique {
+    /// Iterate through every technique, from easiest to hardest.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Technique::HiddenSingle,
+            Technique::NakedSingle,
+            Technique::Pointing,
+            Technique::BoxLineReduction,
+            Technique::NakedPair,
+            Technique::XWing,
+            Technique::HiddenPair,
+            Technique::NakedTriple,
+            Technique::Swordfish,
+            Technique::HiddenTriple,
+            Technique::XYWing,
+            Technique::XYZWing,
+            Technique::WWing,
+            Technique::UniqueRectangle1,
+            Technique::UniqueRectangle2,
+            Technique::UniqueRectangle3,
+            Technique::UniqueRectangle4,
+            Technique::UniqueRectangle5,
+            Technique::UniqueRectangle6,
+            Technique::FinnedXWing,
+            Technique::SashimiXWing,
+            Technique::NakedQuad,
+            Technique::Jellyfish,
+            Technique::HiddenQuad,
+            Technique::BugPlusOne,
+            Technique::FinnedSwordfish,
+            Technique::SashimiSwordfish,
+            Technique::FinnedJellyfish,
+            Technique::SashimiJellyfish,
+            Technique::XChain,
+            Technique::XYChain,
+            Technique::Aic,
+            Technique::AlsXz,
+            Technique::AlsXyWing,
+            Technique::Template,
+            Technique::TemplateCombination,
+        ]
+        .into_iter()
+    }
+
+    /// How hard the technique is, on a scale similar to Sudoku Explainer's, where the easiest
+    /// technique scores 1.5. Harder techniques never score less than easier ones.
+    pub fn difficulty(self) -> f32 {
+        match self {
+            Technique::HiddenSingle => 1.5,
+            Technique::NakedSingle => 2.3,
+            Technique::Pointing => 2.6,
+            Technique::BoxLineReduction => 2.8,
+            Technique::NakedPair => 3.0,
+            Technique::XWing => 3.2,
+            Technique::HiddenPair => 3.4,
+            Technique::NakedTriple => 3.6,
+            Technique::Swordfish => 3.8,
+            Technique::HiddenTriple => 4.0,
+            Technique::XYWing => 4.2,
+            Technique::XYZWing => 4.4,
+            Technique::WWing => 4.4,
+            Technique::UniqueRectangle1 => 4.5,
+            Technique::UniqueRectangle2 => 4.5,
+            Technique::UniqueRectangle3 => 4.5,
+            Technique::UniqueRectangle4 => 4.5,
+            Technique::UniqueRectangle5 => 4.6,
+            Technique::UniqueRectangle6 => 4.6,
+            Technique::FinnedXWing => 4.7,
+            Technique::SashimiXWing => 4.8,
+            Technique::NakedQuad => 5.0,
+            Technique::Jellyfish => 5.2,
+            Technique::HiddenQuad => 5.4,
+            Technique::BugPlusOne => 5.6,
+            Technique::FinnedSwordfish => 5.7,
+            Technique::SashimiSwordfish => 5.8,
+            Technique::FinnedJellyfish => 5.9,
+            Technique::SashimiJellyfish => 6.0,
+            Technique::XChain => 6.6,
+            Technique::XYChain => 6.8,
+            Technique::Aic => 7.0,
+            Technique::AlsXz => 7.5,
+            Technique::AlsXyWing => 8.0,
+            Technique::Template => 8.5,
+            Technique::TemplateCombination => 9.0,
+        }
+    }
+
+    /// Does this technique rely on the puzzle having exactly one solution? Such techniques can
+    /// make mistakes on puzzles with several solutions.
+    pub fn assumes_uniqueness(self) -> bool {
+        matches!(
+            self,
+            Technique::UniqueRectangle1
+                | Technique::UniqueRectangle2
+                | Technique::UniqueRectangle3
+                | Technique::UniqueRectangle4
+                | Technique::UniqueRectangle5
+                | Technique::UniqueRectangle6
+                | Technique::BugPlusOne
+        )
+    }
+
+    /// Look for this technique in the candidates, returning the first deduction it makes. Most
+    /// techniques only need the candidates, but some also need to know where each digit has been
+    /// placed on the board.
+    pub fn find(self, board: &Board, candidates: &Candidates) -> Option<Step> {
+        match self {
+            Technique::HiddenSingle => singles::hidden_single(candidates),
+            Technique::NakedSingle => singles::naked_single(candidates),
+            Technique::Pointing => intersections::pointing(candidates),
+            Technique::BoxLineReduction => intersections::box_line_reduction(candidates),
+            Technique::NakedPair => subsets::naked(candidates, 2),
+            Technique::XWing => fish::basic(candidates, 2),
+            Technique::HiddenPair => subsets::hidden(candidates, 2),
+            Technique::NakedTriple => subsets::naked(candidates, 3),
+            Technique::Swordfish => fish::basic(candidates, 3),
+            Technique::HiddenTriple => subsets::hidden(candidates, 3),
+            Technique::XYWing => wings::xy_wing(candidates),
+            Technique::XYZWing => wings::xyz_wing(candidates),
+            Technique::WWing => wings::w_wing(candidates),
+            Technique::UniqueRectangle1 => uniqueness::type_1(candidates),
+            Technique::UniqueRectangle2 => uniqueness::type_2(candidates),
+            Technique::UniqueRectangle3 => uniqueness::type_3(candidates),
+            Technique::UniqueRectangle4 => uniqueness::type_4(candidates),
+            Technique::UniqueRectangle5 => uniqueness::type_5(candidates),
+            Technique::UniqueRectangle6 => uniqueness::type_6(candidates),
+            Technique::FinnedXWing => fish::finned(candidates, 2, false),
+            Technique::SashimiXWing => fish::finned(candidates, 2, true),
+            Technique::NakedQuad => subsets::naked(candidates, 4),
+            Technique::Jellyfish => fish::basic(candidates, 4),
+            Technique::HiddenQuad => subsets::hidden(candidates, 4),
+            Technique::BugPlusOne => uniqueness::bug(candidates),
+            Technique::FinnedSwordfish => fish::finned(candidates, 3, false),
+            Technique::SashimiSwordfish => fish::finned(candidates, 3, true),
+            Technique::FinnedJellyfish => fish::finned(candidates, 4, false),
+            Technique::SashimiJellyfish => fish::finned(candidates, 4, true),
+            Technique::XChain => chains::x_chain(candidates),
+            Technique::XYChain => chains::xy_chain(candidates),
+            Technique::Aic => chains::aic(candidates),
+            Technique::AlsXz => als::als_xz(candidates),
+            Technique::AlsXyWing => als::als_xy_wing(candidates),
+            Technique::Template => templates::template(board, candidates),
+            Technique::TemplateCombination => templates::template_combination(board, candidates),
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Technique::HiddenSingle => "Hidden Single",
+            Technique::NakedSingle => "Naked Single",
+            Technique::Pointing => "Pointing",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair => "Naked Pair",
+            Technique::XWing => "X-Wing",
+            Technique::HiddenPair => "Hidden Pair",
+            Technique::NakedTriple => "Naked Triple",
+            Technique::Swordfish => "Swordfish",
+            Technique::HiddenTriple => "Hidden Triple",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::UniqueRectangle1 => "Unique Rectangle Type 1",
+            Technique::UniqueRectangle2 => "Unique Rectangle Type 2",
+            Technique::UniqueRectangle3 => "Unique Rectangle Type 3",
+            Technique::UniqueRectangle4 => "Unique Rectangle Type 4",
+            Technique::UniqueRectangle5 => "Unique Rectangle Type 5",
+            Technique::UniqueRectangle6 => "Unique Rectangle Type 6",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::NakedQuad => "Naked Quad",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::HiddenQuad => "Hidden Quad",
+            Technique::BugPlusOne => "BUG+1",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+            Technique::XChain => "X-Chain",
+            Technique::XYChain => "XY-Chain",
+            Technique::Aic => "AIC",
+            Technique::AlsXz => "ALS-XZ",
+            Technique::AlsXyWing => "ALS-XY-Wing",
+            Technique::Template => "Template",
+            Technique::TemplateCombination => "Template Combination",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The conclusion of a single step.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Deduction {
+    /// `digit` can be placed at `cell`, given as `(row, col)`.
+    Placement { cell: (usize, usize), digit: Digit },
+    /// Every candidate set here can be removed.
+    Elimination(Candidates),
+}
+
+/// Extra detail about the pattern behind a step, for techniques whose pattern can't be described
+/// by just its units, cells and digits.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Pattern {
+    /// There is nothing more to the pattern.
+    Basic,
+    /// Within the `base` units, every candidate for the step's digit lies inside the `cover`
+    /// units, apart from the `fins`.
+    Fish {
+        base: Vec<Unit>,
+        cover: Vec<Unit>,
+        fins: Bitfield,
+    },
+    /// A wing, where the `pincers` each share a digit with the `pivot`. For a W-Wing, the pivot is
+    /// the pair of cells which joins the two pincers.
+    Wing { pivot: Bitfield, pincers: Bitfield },
+    /// A chain of candidates, each given as a cell and a digit. The links alternate between
+    /// strong and weak, starting and ending with a strong link, so either the first or the last
+    /// candidate must be true.
+    Chain(Vec<((usize, usize), Digit)>),
+    /// Almost locked sets, each given as its cells, joined by the `restricted` digits which can't
+    /// be in two linked sets at once.
+    Als {
+        sets: Vec<Bitfield>,
+        restricted: Vec<Digit>,
+    },
+    /// The number of templates, or paths, that the step's digit could still take.
+    Templates { count: usize },
+}
+
+/// A single deduction made by the logical solver, along with the reasoning behind it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    /// The technique that found the deduction.
+    pub technique: Technique,
+    /// What the step concludes.
+    pub deduction: Deduction,
+    /// The units that the reasoning is based on.
+    pub units: Vec<Unit>,
+    /// The cells that make up the pattern.
+    pub cells: Bitfield,
+    /// The digits that make up the pattern.
+    pub digits: Vec<Digit>,
+    /// Any extra detail about the pattern that is specific to the technique.
+    pub pattern: Pattern,
+}
+
+// Steps are printed for people, so cells are written as "r1c1", numbered from 1.
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.technique)?;
+        match &self.deduction {
+            Deduction::Placement {
+                cell: (row, col),
+                digit,
+            } => write!(f, "r{}c{}={}", row + 1, col + 1, digit)?,
+            Deduction::Elimination(eliminations) => {
+                let mut first = true;
+                for digit in Digit::iter() {
+                    for (row, col) in eliminations[digit].cells() {
+                        let separator = if first { "" } else { ", " };
+                        write!(f, "{}r{}c{}<>{}", separator, row + 1, col + 1, digit)?;
+                        first = false;
+                    }
+                }
+            }
+        }
+
+        for (index, unit) in self.units.iter().enumerate() {
+            let prefix = if index == 0 { " in " } else { ", " };
+            write!(f, "{}{}", prefix, unit)?;
+        }
+
+        // Chains are written in Eureka notation, with "=" for a strong link and "-" for a weak one
+        if let Pattern::Chain(chain) = &self.pattern {
+            for (index, ((row, col), digit)) in chain.iter().enumerate() {
+                let link = match index {
+                    0 => " via ",
+                    _ if index % 2 == 1 => "=",
+                    _ => "-",
+                };
+                write!(f, "{}({})r{}c{}", link, digit, row + 1, col + 1)?;
+            }
+        }
+
+        if let Pattern::Templates { count } = self.pattern {
+            write!(f, " with {} templates", count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every way of choosing `size` items from `items`, keeping them in their original order.
+fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+
+    (0..items.len())
+        .flat_map(|first| {
+            combinations(&items[first + 1..], size - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, items[first]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// Solves a puzzle one step at a time, keeping track of the placements and pencil marks as it
+/// goes.
+#[derive(Debug, Clone)]
+pub struct LogicalSolver {
+    board: Board,
+    candidates: Candidates,
+    techniques: Vec<Technique>,
+    assume_uniqueness: bool,
+}
+
+impl LogicalSolver {
+    /// Start solving a board, using every technique, although those which assume the puzzle has a
+    /// unique solution stay off until `assume_uniqueness` is called.
+    pub fn new(board: &Board) -> Self {
+        LogicalSolver::with_techniques(board, Technique::iter())
+    }
+
+    /// Start solving a board, only using the given techniques. Easier techniques are always tried
+    /// first, no matter which order they are given in.
+    pub fn with_techniques(board: &Board, techniques: impl IntoIterator<Item = Technique>) -> Self {
+        let mut techniques = techniques.into_iter().collect::<Vec<_>>();
+        techniques.sort();
+        techniques.dedup();
+
+        LogicalSolver {
+            board: board.clone(),
+            candidates: Candidates::new(board),
+            techniques,
+            assume_uniqueness: false,
+        }
+    }
+
+    /// Choose whether to use the techniques which rely on the puzzle having exactly one solution.
+    /// These are off by default, since they can make mistakes on puzzles that don't.
+    pub fn assume_uniqueness(&mut self, assume: bool) {
+        self.assume_uniqueness = assume;
+    }
+
+    /// The board, with every placement made so far.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The pencil marks for every cell which is still empty.
+    pub fn candidates(&self) -> &Candidates {
+        &self.candidates
+    }
+
+    /// Has every cell been filled?
+    pub fn is_solved(&self) -> bool {
+        (!self.board.filled()).is_empty()
+    }
+
+    /// Find the next step, using the easiest technique that applies, without applying it.
+    pub fn next_step(&self) -> Option<Step> {
+        self.techniques
+            .iter()
+            .filter(|technique| self.assume_uniqueness || !technique.assumes_uniqueness())
+            .find_map(|technique| technique.find(&self.board, &self.candidates))
+    }
+
+    /// Apply a step's deduction to the board and candidates.
+    pub fn apply(&mut self, step: &Step) {
+        match step.deduction {
+            Deduction::Placement {
+                cell: (row, col),
+                digit,
+            } => {
+                self.board[digit] |= Bitfield::new(row, col);
+                self.candidates.place(row, col, digit);
+            }
+            Deduction::Elimination(ref eliminations) => self.candidates.remove(eliminations),
+        }
+    }
+
+    /// Find the next step and apply it. Returns None once the solver is stuck, or the puzzle is
+    /// solved.
+    pub fn step(&mut self) -> Option<Step> {
+        let step = self.next_step()?;
+        self.apply(&step);
+        Some(step)
+    }
+
+    /// Keep taking steps for as long as possible, returning every step taken. Check `is_solved`
+    /// afterwards to tell whether the solver finished, or got stuck.
+    pub fn solve(&mut self) -> Vec<Step> {
+        std::iter::from_fn(|| self.step()).collect()
+    }
+}
+
+/// Build a set of candidates from a list of cells and the digits each can hold.
+#[cfg(test)]
+pub(super) fn from_cells(cells: &[((usize, usize), &[Digit])]) -> Candidates {
+    let mut candidates = Candidates::empty();
+    for &((row, col), digits) in cells {
+        for &digit in digits {
+            candidates[digit] |= Bitfield::new(row, col);
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Deduction, LogicalSolver, Technique};
+    use crate::{solver::Solver, Bitfield, Board, Digit};
+
+    /// Puzzles ranging from easy to very hard, which between them need most of the techniques.
+    const PUZZLES: [&str; 7] = [
+        "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
+        "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        "..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9",
+        "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5",
+        ".2.4.37.........32........4.4.2...7.8...5.........1...5.....9...3.9....7..1..86..",
+    ];
+
+    #[test]
+    fn every_step_agrees_with_solution() {
+        for puzzle in PUZZLES {
+            let board = Board::parse(puzzle).unwrap();
+            let solution = Solver::new().solve(&board).unwrap();
+            let mut solver = LogicalSolver::new(&board);
+
+            while let Some(step) = solver.step() {
+                match &step.deduction {
+                    Deduction::Placement {
+                        cell: (row, col),
+                        digit,
+                    } => assert!(
+                        solution[*digit].contains(Bitfield::new(*row, *col)),
+                        "{} is wrong in {}",
+                        step,
+                        puzzle
+                    ),
+                    Deduction::Elimination(eliminations) => assert!(
+                        Digit::iter()
+                            .all(|digit| (eliminations[digit] & solution[digit]).is_empty()),
+                        "{} is wrong in {}",
+                        step,
+                        puzzle
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn uniqueness_techniques_are_opt_in() {
+        let board = Board::parse(
+            "..6.......74..51.61...4.......9...8....25..74..8..4.2..5..9.2...6.413...........7",
+        )
+        .unwrap();
+        let steps = LogicalSolver::new(&board).solve();
+        assert!(steps
+            .iter()
+            .all(|step| !step.technique.assumes_uniqueness()));
+
+        let mut solver = LogicalSolver::new(&board);
+        solver.assume_uniqueness(true);
+        let steps = solver.solve();
+        assert!(solver.is_solved());
+        assert!(steps
+            .iter()
+            .any(|step| step.technique == Technique::UniqueRectangle1));
+        assert_eq!(Ok(solver.board().clone()), Solver::new().solve(&board));
+    }
+
+    #[test]
+    fn solves_easy_puzzle_with_singles() {
+        let board = Board::parse(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        let mut solver = LogicalSolver::new(&board);
+        let steps = solver.solve();
+
+        assert!(solver.is_solved());
+        assert_eq!(steps.len(), 81 - 32);
+        assert_eq!(Ok(solver.board().clone()), Solver::new().solve(&board));
+    }
+
+    #[test]
+    fn only_uses_allowed_techniques() {
+        let board = Board::parse(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        let mut solver = LogicalSolver::with_techniques(&board, [Technique::NakedSingle]);
+        let steps = solver.solve();
+
+        assert!(steps
+            .iter()
+            .all(|step| step.technique == Technique::NakedSingle));
+    }
+
+    #[test]
+    fn step_display() {
+        let board = Board::parse(
+            "12345678.........................................................................",
+        )
+        .unwrap();
+        let step = LogicalSolver::new(&board).next_step().unwrap();
+
+        assert_eq!(step.to_string(), "Hidden Single: r1c9=9 in row 1");
+    }
+}
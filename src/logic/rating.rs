@@ -0,0 +1,183 @@
+use super::{LogicalSolver, Technique};
+use crate::Board;
+use std::fmt::{Display, Formatter};
+
+/// How hard a puzzle is to solve by hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    /// The hardest technique that the puzzle needed, or None if it needed no steps at all.
+    pub hardest: Option<Technique>,
+    /// The difficulty of the hardest technique, or 0 if no steps were needed. If the puzzle wasn't
+    /// finished, this is `Rating::UNFINISHED` instead, so that it sorts above every finished one.
+    pub score: f32,
+    /// The number of steps taken.
+    pub steps: usize,
+    /// Did the logical solver finish the puzzle? If not, the puzzle is harder than every
+    /// technique it knows.
+    pub solved: bool,
+}
+
+impl Rating {
+    /// The score given to a puzzle that the logical solver couldn't finish, which is higher than
+    /// the difficulty of any technique.
+    pub const UNFINISHED: f32 = 10.0;
+}
+
+/// Rate a puzzle by solving it with the logical solver, which always takes the easiest step it can
+/// find, and scoring it by the hardest technique it had to use. Techniques which rely on the
+/// puzzle having a unique solution are allowed, as they would be for a person.
+///
+/// Returns None if the board doesn't have exactly one solution, since then it isn't a proper
+/// puzzle, and how far the logical solver gets says nothing about how hard it is.
+pub fn rate(board: &Board) -> Option<Rating> {
+    rate_with(board, Technique::iter())
+}
+
+/// Rate a puzzle like `rate`, but only using the given techniques. A puzzle that needs any other
+/// technique won't be solved.
+pub fn rate_with(board: &Board, techniques: impl IntoIterator<Item = Technique>) -> Option<Rating> {
+    if !board.is_unique().is_unique() {
+        return None;
+    }
+
+    let mut solver = LogicalSolver::with_techniques(board, techniques);
+    solver.assume_uniqueness(true);
+
+    let steps = solver.solve();
+    let hardest = steps.iter().map(|step| step.technique).max();
+    let solved = solver.is_solved();
+    let score = if solved {
+        hardest.map_or(0.0, Technique::difficulty)
+    } else {
+        Rating::UNFINISHED
+    };
+
+    Some(Rating {
+        hardest,
+        score,
+        steps: steps.len(),
+        solved,
+    })
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1}", self.score)?;
+        match (self.solved, self.hardest) {
+            (false, _) => f.write_str(", unfinished"),
+            (true, Some(hardest)) => write!(f, " ({})", hardest),
+            (true, None) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rate, rate_with, Rating};
+    use crate::{logic::Technique, Board};
+
+    #[test]
+    fn scores_never_decrease() {
+        let scores = Technique::iter()
+            .map(Technique::difficulty)
+            .collect::<Vec<_>>();
+        assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(Technique::iter().all(|technique| technique.difficulty() < Rating::UNFINISHED));
+    }
+
+    #[test]
+    fn rates_easy_puzzle() {
+        let board = Board::parse(
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+        )
+        .unwrap();
+        let rating = rate(&board).unwrap();
+
+        assert!(rating.solved);
+        assert_eq!(rating.steps, 81 - 32);
+        assert!(rating.score <= Technique::NakedSingle.difficulty());
+    }
+
+    #[test]
+    fn harder_puzzles_score_higher() {
+        let easy = rate(
+            &Board::parse(
+                "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let hard = rate(
+            &Board::parse(
+                "..6.......74..51.61...4.......9...8....25..74..8..4.2..5..9.2...6.413...........7",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(hard.solved);
+        assert_eq!(hard.hardest, Some(Technique::UniqueRectangle1));
+        assert!(hard.score > easy.score);
+    }
+
+    #[test]
+    fn rates_with_fewer_techniques() {
+        let board = Board::parse(
+            "..6.......74..51.61...4.......9...8....25..74..8..4.2..5..9.2...6.413...........7",
+        )
+        .unwrap();
+        let rating = rate_with(&board, [Technique::HiddenSingle, Technique::NakedSingle]).unwrap();
+
+        assert!(!rating.solved);
+        assert_eq!(rating.score, Rating::UNFINISHED);
+    }
+
+    #[test]
+    fn reports_unfinished_puzzle() {
+        let board = Board::parse(
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        )
+        .unwrap();
+        let rating = rate(&board).unwrap();
+        let easy = rate(
+            &Board::parse(
+                "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3..",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(!rating.solved);
+        assert!(rating.score > easy.score);
+        assert_eq!(rating.to_string(), "10.0, unfinished");
+    }
+
+    #[test]
+    fn solved_board_needs_no_steps() {
+        let board = Board::parse(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        let rating = rate(&board).unwrap();
+
+        assert!(rating.solved);
+        assert_eq!(rating.hardest, None);
+        assert_eq!(rating.to_string(), "0.0");
+    }
+
+    #[test]
+    fn rejects_boards_without_one_solution() {
+        let unsolvable = Board::parse(
+            "1.......1........................................................................",
+        )
+        .unwrap();
+        let ambiguous = Board::parse(
+            "12345678.........................................................................",
+        )
+        .unwrap();
+
+        assert_eq!(rate(&unsolvable), None);
+        assert_eq!(rate(&ambiguous), None);
+        assert_eq!(rate(&Board::parse(&".".repeat(81)).unwrap()), None);
+    }
+}
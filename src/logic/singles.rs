@@ -0,0 +1,98 @@
+use super::{Deduction, Pattern, Step, Technique};
+use crate::{Bitfield, Candidates, Digit, Unit};
+
+/// Find a unit where a digit only has one cell left that it can go in.
+pub(super) fn hidden_single(candidates: &Candidates) -> Option<Step> {
+    Unit::iter().find_map(|unit| {
+        Digit::iter().find_map(|digit| {
+            let cells = candidates[digit] & unit.cells();
+            if cells.len() != 1 {
+                return None;
+            }
+
+            let cell = cells.cells().next()?;
+            Some(Step {
+                technique: Technique::HiddenSingle,
+                deduction: Deduction::Placement { cell, digit },
+                units: vec![unit],
+                cells,
+                digits: vec![digit],
+                pattern: Pattern::Basic,
+            })
+        })
+    })
+}
+
+/// Find a cell which only has one digit left that can go in it.
+pub(super) fn naked_single(candidates: &Candidates) -> Option<Step> {
+    candidates.cells().cells().find_map(|(row, col)| {
+        let mut digits = candidates.at(row, col);
+        let digit = digits.next()?;
+        if digits.next().is_some() {
+            return None;
+        }
+
+        Some(Step {
+            technique: Technique::NakedSingle,
+            deduction: Deduction::Placement {
+                cell: (row, col),
+                digit,
+            },
+            units: Unit::containing(row, col).to_vec(),
+            cells: Bitfield::new(row, col),
+            digits: vec![digit],
+            pattern: Pattern::Basic,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hidden_single, naked_single};
+    use crate::{
+        logic::{Deduction, Technique},
+        Board, Candidates, Digit, Unit,
+    };
+
+    #[test]
+    fn finds_naked_single() {
+        // The top left cell sees every digit but 9, although a 9 could go elsewhere in its units
+        let board = Board::parse(
+            ".1234....5........6........7........8............................................",
+        )
+        .unwrap();
+        let step = naked_single(&Candidates::new(&board)).unwrap();
+
+        assert_eq!(step.technique, Technique::NakedSingle);
+        assert_eq!(
+            step.deduction,
+            Deduction::Placement {
+                cell: (0, 0),
+                digit: Digit::_9
+            }
+        );
+        assert_eq!(step.units, vec![Unit::Row(0), Unit::Col(0), Unit::Box(0)]);
+    }
+
+    #[test]
+    fn finds_hidden_single() {
+        // The other 1s leave the top left cell as the only home for a 1 in the top row, though
+        // other digits could still go there too
+        let board = Board::parse(
+            ".............1...........1..1...........................1........................",
+        )
+        .unwrap();
+        let candidates = Candidates::new(&board);
+        let step = hidden_single(&candidates).unwrap();
+
+        assert!(naked_single(&candidates).is_none());
+        assert_eq!(
+            step.deduction,
+            Deduction::Placement {
+                cell: (0, 0),
+                digit: Digit::_1
+            }
+        );
+        assert_eq!(step.units, vec![Unit::Row(0)]);
+    }
+}
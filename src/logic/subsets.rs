@@ -0,0 +1,197 @@
+use super::{combinations, Deduction, Pattern, Step, Technique};
+use crate::{Bitfield, Candidates, Digit, Unit};
+use std::ops::BitOr;
+
+/// The technique for a naked subset of the given size.
+fn naked_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::NakedPair,
+        3 => Technique::NakedTriple,
+        4 => Technique::NakedQuad,
+        _ => panic!("no technique for a naked subset of {} cells", size),
+    }
+}
+
+/// The technique for a hidden subset of the given size.
+fn hidden_technique(size: usize) -> Technique {
+    match size {
+        2 => Technique::HiddenPair,
+        3 => Technique::HiddenTriple,
+        4 => Technique::HiddenQuad,
+        _ => panic!("no technique for a hidden subset of {} digits", size),
+    }
+}
+
+/// Find `size` cells in a unit which, between them, can only hold `size` digits. Those digits have
+/// to go in those cells, so they can be removed from the rest of the unit.
+pub(super) fn naked(candidates: &Candidates, size: usize) -> Option<Step> {
+    Unit::iter().find_map(|unit| {
+        let empty = candidates.cells() & unit.cells();
+        let cells = empty
+            .cells()
+            .map(|(row, col)| Bitfield::new(row, col))
+            .collect::<Vec<_>>();
+        if cells.len() <= size {
+            return None;
+        }
+
+        combinations(&cells, size).into_iter().find_map(|subset| {
+            let subset = subset.into_iter().fold(Bitfield::default(), BitOr::bitor);
+            let digits = Digit::iter()
+                .filter(|&digit| !(candidates[digit] & subset).is_empty())
+                .collect::<Vec<_>>();
+            if digits.len() != size {
+                return None;
+            }
+
+            let mut eliminations = Candidates::empty();
+            for &digit in &digits {
+                eliminations[digit] = candidates[digit] & empty & !subset;
+            }
+            if eliminations.is_empty() {
+                return None;
+            }
+
+            Some(Step {
+                technique: naked_technique(size),
+                deduction: Deduction::Elimination(eliminations),
+                units: vec![unit],
+                cells: subset,
+                digits,
+                pattern: Pattern::Basic,
+            })
+        })
+    })
+}
+
+/// Find `size` digits which, between them, can only go in `size` cells of a unit. Those cells have
+/// to hold those digits, so every other digit can be removed from them.
+pub(super) fn hidden(candidates: &Candidates, size: usize) -> Option<Step> {
+    Unit::iter().find_map(|unit| {
+        let digits = Digit::iter()
+            .filter(|&digit| !(candidates[digit] & unit.cells()).is_empty())
+            .collect::<Vec<_>>();
+        if digits.len() <= size {
+            return None;
+        }
+
+        combinations(&digits, size).into_iter().find_map(|subset| {
+            let cells = subset
+                .iter()
+                .map(|&digit| candidates[digit] & unit.cells())
+                .fold(Bitfield::default(), BitOr::bitor);
+            if cells.len() as usize != size {
+                return None;
+            }
+
+            let mut eliminations = Candidates::empty();
+            for digit in Digit::iter().filter(|digit| !subset.contains(digit)) {
+                eliminations[digit] = candidates[digit] & cells;
+            }
+            if eliminations.is_empty() {
+                return None;
+            }
+
+            Some(Step {
+                technique: hidden_technique(size),
+                deduction: Deduction::Elimination(eliminations),
+                units: vec![unit],
+                cells,
+                digits: subset,
+                pattern: Pattern::Basic,
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hidden, naked};
+    use crate::{
+        logic::{Deduction, Technique},
+        Bitfield, Candidates, Digit, Unit,
+    };
+
+    #[test]
+    fn finds_naked_pair() {
+        // Row 0: two cells that can only be 1 or 2, and two more that could be anything
+        let mut candidates = Candidates::empty();
+        let pair = Bitfield::new(0, 1) | Bitfield::new(0, 6);
+        let others = Bitfield::new(0, 3) | Bitfield::new(0, 8);
+        for digit in Digit::iter() {
+            candidates[digit] = others;
+        }
+        candidates[Digit::_1] |= pair;
+        candidates[Digit::_2] |= pair;
+
+        let step = naked(&candidates, 2).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_1] = others;
+        eliminations[Digit::_2] = others;
+        assert_eq!(step.technique, Technique::NakedPair);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.units, vec![Unit::Row(0)]);
+        assert_eq!(step.cells, pair);
+        assert_eq!(step.digits, vec![Digit::_1, Digit::_2]);
+    }
+
+    #[test]
+    fn finds_hidden_triple() {
+        // Column 4: 3, 5 and 7 can only go in three cells, which could also hold other digits
+        let mut candidates = Candidates::empty();
+        let column = Unit::Col(4).cells();
+        let triple = Bitfield::new(1, 4) | Bitfield::new(4, 4) | Bitfield::new(7, 4);
+        for digit in Digit::iter() {
+            candidates[digit] = column;
+        }
+        candidates[Digit::_3] = Bitfield::new(1, 4) | Bitfield::new(4, 4);
+        candidates[Digit::_5] = Bitfield::new(4, 4) | Bitfield::new(7, 4);
+        candidates[Digit::_7] = triple;
+
+        assert!(hidden(&candidates, 2).is_none());
+        let step = hidden(&candidates, 3).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        for digit in [
+            Digit::_1,
+            Digit::_2,
+            Digit::_4,
+            Digit::_6,
+            Digit::_8,
+            Digit::_9,
+        ] {
+            eliminations[digit] = triple;
+        }
+        assert_eq!(step.technique, Technique::HiddenTriple);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.units, vec![Unit::Col(4)]);
+        assert_eq!(step.digits, vec![Digit::_3, Digit::_5, Digit::_7]);
+    }
+
+    #[test]
+    fn finds_naked_quad() {
+        // Box 8: four cells share the digits 2, 4, 6 and 8 between them
+        let mut candidates = Candidates::empty();
+        let quad =
+            Bitfield::new(6, 6) | Bitfield::new(6, 7) | Bitfield::new(7, 6) | Bitfield::new(8, 8);
+        let others = Bitfield::new(7, 8) | Bitfield::new(8, 6);
+        candidates[Digit::_2] = Bitfield::new(6, 6) | Bitfield::new(6, 7) | others;
+        candidates[Digit::_4] = Bitfield::new(6, 7) | Bitfield::new(7, 6);
+        candidates[Digit::_6] = Bitfield::new(7, 6) | Bitfield::new(8, 8) | others;
+        candidates[Digit::_8] = Bitfield::new(8, 8) | Bitfield::new(6, 6);
+        candidates[Digit::_9] = others;
+        candidates[Digit::_1] = others;
+
+        assert!(naked(&candidates, 2).is_none());
+        assert!(naked(&candidates, 3).is_none());
+        let step = naked(&candidates, 4).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_2] = others;
+        eliminations[Digit::_6] = others;
+        assert_eq!(step.technique, Technique::NakedQuad);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.cells, quad);
+    }
+}
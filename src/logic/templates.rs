@@ -0,0 +1,162 @@
+use super::{Deduction, Pattern, Step, Technique};
+use crate::{Bitfield, Board, Candidates, Digit, PathDb};
+
+/// Every template, or path, that each digit could still take: those through all of its
+/// placements, and otherwise only through cells where it is still a candidate.
+fn templates(board: &Board, candidates: &Candidates) -> Vec<(Digit, Vec<Bitfield>)> {
+    let db = PathDb::shared();
+    Digit::iter()
+        .map(|digit| {
+            let allowed = board[digit] | candidates[digit];
+            (digit, db.matching(board[digit], !allowed))
+        })
+        .collect()
+}
+
+/// Throw out every template for a digit which overlaps with every remaining template for some
+/// other digit, since the two digits can't both be placed.
+fn combine(possible: &[(Digit, Vec<Bitfield>)]) -> Vec<(Digit, Vec<Bitfield>)> {
+    possible
+        .iter()
+        .map(|(digit, paths)| {
+            let paths = paths
+                .iter()
+                .cloned()
+                .filter(|&path| {
+                    possible
+                        .iter()
+                        .filter(|(other, _)| other != digit)
+                        .all(|(_, others)| others.iter().any(|&other| (other & path).is_empty()))
+                })
+                .collect();
+            (*digit, paths)
+        })
+        .collect()
+}
+
+/// Find a digit whose templates all pass through an empty cell, so the digit must go there, or
+/// which has a candidate that none of its templates pass through, so it can be removed.
+fn deduce(
+    technique: Technique,
+    board: &Board,
+    candidates: &Candidates,
+    possible: Vec<(Digit, Vec<Bitfield>)>,
+) -> Option<Step> {
+    for (digit, paths) in possible {
+        if paths.is_empty() {
+            continue;
+        }
+
+        let every = paths
+            .iter()
+            .fold(!Bitfield::default(), |acc, &path| acc & path);
+        let any = paths
+            .iter()
+            .fold(Bitfield::default(), |acc, &path| acc | path);
+
+        let deduction = if let Some(cell) = (every & !board[digit]).cells().next() {
+            Deduction::Placement { cell, digit }
+        } else {
+            let removed = candidates[digit] & !any;
+            if removed.is_empty() {
+                continue;
+            }
+
+            let mut eliminations = Candidates::empty();
+            eliminations[digit] = removed;
+            Deduction::Elimination(eliminations)
+        };
+
+        return Some(Step {
+            technique,
+            deduction,
+            units: Vec::new(),
+            cells: any & !board[digit],
+            digits: vec![digit],
+            pattern: Pattern::Templates { count: paths.len() },
+        });
+    }
+
+    None
+}
+
+/// Compare a digit's candidates against the templates it could still take. Any cell that every
+/// template passes through must hold the digit, and any candidate that no template passes through
+/// can be removed.
+pub(super) fn template(board: &Board, candidates: &Candidates) -> Option<Step> {
+    let possible = templates(board, candidates);
+    deduce(Technique::Template, board, candidates, possible)
+}
+
+/// Like `template`, but first discard every template which can't be combined with any template of
+/// some other digit.
+pub(super) fn template_combination(board: &Board, candidates: &Candidates) -> Option<Step> {
+    let possible = combine(&templates(board, candidates));
+    deduce(Technique::TemplateCombination, board, candidates, possible)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{combine, template, templates};
+    use crate::{
+        logic::{Deduction, Pattern, Technique},
+        solver::Solver,
+        Bitfield, Board, Candidates, Digit,
+    };
+
+    #[test]
+    fn templates_include_solution() {
+        let board = Board::parse(
+            "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3",
+        )
+        .unwrap();
+        let candidates = Candidates::new(&board);
+        let solution = Solver::new().solve(&board).unwrap();
+
+        for (digit, paths) in combine(&templates(&board, &candidates)) {
+            assert!(paths.contains(&solution[digit]));
+        }
+    }
+
+    #[test]
+    fn finds_forced_placement() {
+        // Seven 9s have been placed, leaving two ways to place the last two, one of which has
+        // lost a candidate
+        let board = Board::parse(
+            "9...........9...........9...9...........9...........9...9........................",
+        )
+        .unwrap();
+        let mut candidates = Candidates::new(&board);
+        assert!(template(&board, &candidates).is_none());
+
+        candidates[Digit::_9] &= !Bitfield::new(7, 5);
+        let step = template(&board, &candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::Template);
+        assert_eq!(
+            step.deduction,
+            Deduction::Placement {
+                cell: (7, 8),
+                digit: Digit::_9
+            }
+        );
+        assert_eq!(step.pattern, Pattern::Templates { count: 1 });
+    }
+
+    #[test]
+    fn removes_candidates_outside_every_template() {
+        let board = Board::parse(
+            "9...........9...........9...9...........9...........9...9........................",
+        )
+        .unwrap();
+        let mut candidates = Candidates::new(&board);
+        candidates[Digit::_9] |= Bitfield::new(8, 0);
+        let step = template(&board, &candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[Digit::_9] = Bitfield::new(8, 0);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.digits, vec![Digit::_9]);
+        assert_eq!(step.pattern, Pattern::Templates { count: 2 });
+    }
+}
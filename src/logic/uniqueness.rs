@@ -0,0 +1,485 @@
+use super::{combinations, Deduction, Pattern, Step, Technique};
+use crate::{peers, Bitfield, Candidates, Digit, Unit};
+
+/// Four empty cells at the corners of a rectangle, spanning two rows, two columns and two boxes,
+/// which can all hold both of the same two digits. If those were the only digits the corners
+/// could hold, the two digits could be swapped around the rectangle, and the puzzle would not
+/// have a unique solution.
+struct Rectangle {
+    /// The corners, in reading order.
+    corners: [(usize, usize); 4],
+    digits: [Digit; 2],
+}
+
+impl Rectangle {
+    fn cells(&self) -> Bitfield {
+        self.corners
+            .iter()
+            .fold(Bitfield::default(), |cells, &(row, col)| {
+                cells | Bitfield::new(row, col)
+            })
+    }
+
+    /// The digits other than the rectangle's own two that could go in a corner.
+    fn extras(&self, candidates: &Candidates, (row, col): (usize, usize)) -> Vec<Digit> {
+        candidates
+            .at(row, col)
+            .filter(|digit| !self.digits.contains(digit))
+            .collect()
+    }
+
+    /// The corners which could hold some other digit, and so stop the rectangle from being
+    /// deadly.
+    fn roof(&self, candidates: &Candidates) -> Vec<(usize, usize)> {
+        self.corners
+            .iter()
+            .cloned()
+            .filter(|&corner| !self.extras(candidates, corner).is_empty())
+            .collect()
+    }
+
+    fn step(&self, technique: Technique, units: Vec<Unit>, removed: Candidates) -> Option<Step> {
+        if removed.is_empty() {
+            return None;
+        }
+
+        Some(Step {
+            technique,
+            deduction: Deduction::Elimination(removed),
+            units,
+            cells: self.cells(),
+            digits: self.digits.to_vec(),
+            pattern: Pattern::Basic,
+        })
+    }
+}
+
+/// Every rectangle in the candidates.
+fn rectangles(candidates: &Candidates) -> Vec<Rectangle> {
+    let mut output = Vec::new();
+    for top in 0..9 {
+        for bottom in top + 1..9 {
+            for left in 0..9 {
+                for right in left + 1..9 {
+                    if (top / 3 == bottom / 3) == (left / 3 == right / 3) {
+                        continue;
+                    }
+
+                    let corners = [(top, left), (top, right), (bottom, left), (bottom, right)];
+                    let cells = corners
+                        .iter()
+                        .fold(Bitfield::default(), |cells, &(row, col)| {
+                            cells | Bitfield::new(row, col)
+                        });
+                    let digits = Digit::iter()
+                        .filter(|&digit| candidates[digit].contains(cells))
+                        .collect::<Vec<_>>();
+
+                    for pair in combinations(&digits, 2) {
+                        output.push(Rectangle {
+                            corners,
+                            digits: [pair[0], pair[1]],
+                        });
+                    }
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Every cell that sees all of the given cells.
+fn common_peers(cells: &[(usize, usize)]) -> Bitfield {
+    cells.iter().fold(!Bitfield::default(), |acc, &(row, col)| {
+        acc & peers(row, col)
+    })
+}
+
+/// The units that both cells are in.
+fn shared_units(first: (usize, usize), second: (usize, usize)) -> Vec<Unit> {
+    Unit::containing(first.0, first.1)
+        .into_iter()
+        .filter(|unit| unit.cells().contains(Bitfield::new(second.0, second.1)))
+        .collect()
+}
+
+/// Is a pair of corners on the same side of the rectangle, rather than diagonally opposite?
+fn same_side(first: (usize, usize), second: (usize, usize)) -> bool {
+    first.0 == second.0 || first.1 == second.1
+}
+
+/// Find a rectangle where only one corner has any other digits. That corner must hold one of
+/// them, so the rectangle's two digits can be removed from it.
+pub(super) fn type_1(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let [(row, col)] = rectangle.roof(candidates)[..] else {
+            return None;
+        };
+
+        let mut removed = Candidates::empty();
+        for digit in rectangle.digits {
+            removed[digit] = Bitfield::new(row, col);
+        }
+        rectangle.step(Technique::UniqueRectangle1, Vec::new(), removed)
+    })
+}
+
+/// Find a rectangle where two corners on the same side both have one other digit, the same one.
+/// One of them must hold that digit, so it can be removed from every cell that sees both.
+pub(super) fn type_2(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let roof = rectangle.roof(candidates);
+        let [first, second] = roof[..] else {
+            return None;
+        };
+        if !same_side(first, second) {
+            return None;
+        }
+
+        let extras = rectangle.extras(candidates, first);
+        let [digit] = extras[..] else {
+            return None;
+        };
+        if rectangle.extras(candidates, second) != extras {
+            return None;
+        }
+
+        let mut removed = Candidates::empty();
+        removed[digit] = candidates[digit] & common_peers(&roof);
+        rectangle.step(Technique::UniqueRectangle2, Vec::new(), removed)
+    })
+}
+
+/// Find a rectangle where the other digits in two corners on the same side form a naked subset
+/// with some other cells in a unit they share. One of the two corners must hold one of those
+/// digits, so together they act as a single cell.
+pub(super) fn type_3(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let roof = rectangle.roof(candidates);
+        let [first, second] = roof[..] else {
+            return None;
+        };
+        if !same_side(first, second) {
+            return None;
+        }
+
+        let mut extras = rectangle.extras(candidates, first);
+        extras.extend(rectangle.extras(candidates, second));
+        extras.sort();
+        extras.dedup();
+
+        let roof_cells = Bitfield::new(first.0, first.1) | Bitfield::new(second.0, second.1);
+        shared_units(first, second).into_iter().find_map(|unit| {
+            let others = (candidates.cells() & unit.cells() & !roof_cells)
+                .cells()
+                .collect::<Vec<_>>();
+
+            (1..=3).find_map(|size| {
+                combinations(&others, size).into_iter().find_map(|subset| {
+                    let mut digits = extras.clone();
+                    digits.extend(
+                        subset
+                            .iter()
+                            .flat_map(|&(row, col)| candidates.at(row, col)),
+                    );
+                    digits.sort();
+                    digits.dedup();
+                    if digits.len() != size + 1 {
+                        return None;
+                    }
+
+                    let subset_cells = subset.iter().fold(roof_cells, |cells, &(row, col)| {
+                        cells | Bitfield::new(row, col)
+                    });
+                    let mut removed = Candidates::empty();
+                    for digit in digits {
+                        removed[digit] = candidates[digit] & unit.cells() & !subset_cells;
+                    }
+                    rectangle.step(Technique::UniqueRectangle3, vec![unit], removed)
+                })
+            })
+        })
+    })
+}
+
+/// Find a rectangle where two corners on the same side share a unit in which one of the
+/// rectangle's digits can only go in those two corners. One of them must hold that digit, so the
+/// other digit would make the rectangle deadly, and can be removed from both.
+pub(super) fn type_4(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let [first, second] = rectangle.roof(candidates)[..] else {
+            return None;
+        };
+        if !same_side(first, second) {
+            return None;
+        }
+
+        let roof_cells = Bitfield::new(first.0, first.1) | Bitfield::new(second.0, second.1);
+        shared_units(first, second).into_iter().find_map(|unit| {
+            let [x, y] = rectangle.digits;
+            [(x, y), (y, x)].into_iter().find_map(|(locked, other)| {
+                if candidates[locked] & unit.cells() != roof_cells {
+                    return None;
+                }
+
+                let mut removed = Candidates::empty();
+                removed[other] = candidates[other] & roof_cells;
+                rectangle.step(Technique::UniqueRectangle4, vec![unit], removed)
+            })
+        })
+    })
+}
+
+/// Find a rectangle where two diagonally opposite corners, or three corners, have exactly one
+/// other digit, the same one. One of them must hold it, so it can be removed from every cell that
+/// sees all of them.
+pub(super) fn type_5(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let roof = rectangle.roof(candidates);
+        match roof[..] {
+            [first, second] if !same_side(first, second) => {}
+            [_, _, _] => {}
+            _ => return None,
+        }
+
+        let extras = rectangle.extras(candidates, roof[0]);
+        let [digit] = extras[..] else {
+            return None;
+        };
+        if roof
+            .iter()
+            .any(|&corner| rectangle.extras(candidates, corner) != extras)
+        {
+            return None;
+        }
+
+        let mut removed = Candidates::empty();
+        removed[digit] = candidates[digit] & common_peers(&roof);
+        rectangle.step(Technique::UniqueRectangle5, Vec::new(), removed)
+    })
+}
+
+/// Find a rectangle where two diagonally opposite corners have other digits, and one of the
+/// rectangle's digits can only go in the corners within both of its rows and both of its columns.
+/// If either of those two corners held that digit then so would the other, leaving the rectangle
+/// deadly, so it can be removed from both.
+pub(super) fn type_6(candidates: &Candidates) -> Option<Step> {
+    rectangles(candidates).into_iter().find_map(|rectangle| {
+        let [first, second] = rectangle.roof(candidates)[..] else {
+            return None;
+        };
+        if same_side(first, second) {
+            return None;
+        }
+
+        let [(top, left), _, _, (bottom, right)] = rectangle.corners;
+        let lines = [
+            Unit::Row(top),
+            Unit::Row(bottom),
+            Unit::Col(left),
+            Unit::Col(right),
+        ];
+        let cells = rectangle.cells();
+        rectangle.digits.into_iter().find_map(|digit| {
+            if lines
+                .iter()
+                .any(|line| !cells.contains(candidates[digit] & line.cells()))
+            {
+                return None;
+            }
+
+            let mut removed = Candidates::empty();
+            removed[digit] = Bitfield::new(first.0, first.1) | Bitfield::new(second.0, second.1);
+            rectangle.step(Technique::UniqueRectangle6, lines.to_vec(), removed)
+        })
+    })
+}
+
+/// Find a cell with three candidates, when every other empty cell has two. If taking away one of
+/// those three would leave every digit with either zero or two places in every unit, then the
+/// puzzle would have no solution or several, so the cell must hold that digit.
+pub(super) fn bug(candidates: &Candidates) -> Option<Step> {
+    let mut odd = None;
+    for (row, col) in candidates.cells().cells() {
+        match candidates.at(row, col).count() {
+            2 => {}
+            3 if odd.is_none() => odd = Some((row, col)),
+            _ => return None,
+        }
+    }
+    let (row, col) = odd?;
+
+    let digit = candidates.at(row, col).find(|&digit| {
+        let mut remaining = candidates.clone();
+        remaining[digit] &= !Bitfield::new(row, col);
+        Unit::iter().all(|unit| {
+            Digit::iter().all(|other| matches!((remaining[other] & unit.cells()).len(), 0 | 2))
+        })
+    })?;
+
+    Some(Step {
+        technique: Technique::BugPlusOne,
+        deduction: Deduction::Placement {
+            cell: (row, col),
+            digit,
+        },
+        units: Vec::new(),
+        cells: candidates.cells(),
+        digits: vec![digit],
+        pattern: Pattern::Basic,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bug, type_1, type_2, type_3, type_4, type_5, type_6};
+    use crate::{
+        logic::{from_cells, Deduction, Technique},
+        Bitfield, Candidates, Digit,
+    };
+
+    /// Build the deduction that removes each digit from its cell.
+    fn eliminations(removed: &[((usize, usize), Digit)]) -> Deduction {
+        let mut candidates = Candidates::empty();
+        for &((row, col), digit) in removed {
+            candidates[digit] |= Bitfield::new(row, col);
+        }
+        Deduction::Elimination(candidates)
+    }
+
+    #[test]
+    fn finds_type_1() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2]),
+            ((1, 0), &[_1, _2]),
+            ((1, 3), &[_1, _2, _5]),
+        ]);
+        let step = type_1(&candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::UniqueRectangle1);
+        assert_eq!(step.deduction, eliminations(&[((1, 3), _1), ((1, 3), _2)]));
+    }
+
+    #[test]
+    fn finds_type_2() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2]),
+            ((1, 0), &[_1, _2, _5]),
+            ((1, 3), &[_1, _2, _5]),
+            ((1, 6), &[_5, _7]),
+            ((4, 0), &[_5, _8]),
+        ]);
+        assert!(type_1(&candidates).is_none());
+        let step = type_2(&candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::UniqueRectangle2);
+        assert_eq!(step.deduction, eliminations(&[((1, 6), _5)]));
+    }
+
+    #[test]
+    fn finds_type_3() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2]),
+            ((1, 0), &[_1, _2, _5]),
+            ((1, 3), &[_1, _2, _6]),
+            ((1, 6), &[_5, _6]),
+            ((1, 8), &[_5, _6, _7]),
+        ]);
+        let step = type_3(&candidates).unwrap();
+
+        // The roof acts as a cell holding a 5 or a 6, making a naked pair with r2c7
+        assert_eq!(step.technique, Technique::UniqueRectangle3);
+        assert_eq!(step.deduction, eliminations(&[((1, 8), _5), ((1, 8), _6)]));
+    }
+
+    #[test]
+    fn finds_type_4() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2]),
+            ((1, 0), &[_1, _2, _5]),
+            ((1, 3), &[_1, _2, _6]),
+            ((1, 6), &[_2, _5, _6]),
+        ]);
+        let step = type_4(&candidates).unwrap();
+
+        // The only 1s in the second row are in the roof
+        assert_eq!(step.technique, Technique::UniqueRectangle4);
+        assert_eq!(step.deduction, eliminations(&[((1, 0), _2), ((1, 3), _2)]));
+    }
+
+    #[test]
+    fn finds_type_5() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2, _5]),
+            ((1, 0), &[_1, _2, _5]),
+            ((1, 3), &[_1, _2]),
+            ((0, 1), &[_5, _7]),
+            ((1, 6), &[_5, _7]),
+        ]);
+        assert!(type_2(&candidates).is_none());
+        let step = type_5(&candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::UniqueRectangle5);
+        assert_eq!(step.deduction, eliminations(&[((0, 1), _5)]));
+    }
+
+    #[test]
+    fn finds_type_6() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2, _5]),
+            ((1, 0), &[_1, _2, _6]),
+            ((1, 3), &[_1, _2]),
+            ((0, 6), &[_2, _7]),
+        ]);
+        let step = type_6(&candidates).unwrap();
+
+        // The 1s form an X-Wing, so they have to go on one of the diagonals
+        assert_eq!(step.technique, Technique::UniqueRectangle6);
+        assert_eq!(step.deduction, eliminations(&[((0, 3), _1), ((1, 0), _1)]));
+    }
+
+    #[test]
+    fn finds_bug_plus_one() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2]),
+            ((1, 0), &[_1, _2]),
+            ((1, 3), &[_1, _2, _3]),
+        ]);
+        let step = bug(&candidates).unwrap();
+
+        assert_eq!(step.technique, Technique::BugPlusOne);
+        assert_eq!(
+            step.deduction,
+            Deduction::Placement {
+                cell: (1, 3),
+                digit: _3
+            }
+        );
+    }
+
+    #[test]
+    fn no_bug_with_two_extra_cells() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 3), &[_1, _2, _4]),
+            ((1, 0), &[_1, _2]),
+            ((1, 3), &[_1, _2, _3]),
+        ]);
+        assert!(bug(&candidates).is_none());
+    }
+}
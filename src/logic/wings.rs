@@ -0,0 +1,304 @@
+use super::{Deduction, Pattern, Step, Technique};
+use crate::{peers, Bitfield, Candidates, Digit, Unit};
+
+/// A cell, and every digit that could still go in it.
+struct Cell {
+    position: (usize, usize),
+    digits: Vec<Digit>,
+}
+
+impl Cell {
+    fn bitfield(&self) -> Bitfield {
+        Bitfield::new(self.position.0, self.position.1)
+    }
+
+    fn peers(&self) -> Bitfield {
+        peers(self.position.0, self.position.1)
+    }
+
+    fn sees(&self, other: &Cell) -> bool {
+        self.peers().contains(other.bitfield())
+    }
+}
+
+/// Every empty cell with exactly `count` candidates.
+fn cells_with(candidates: &Candidates, count: usize) -> Vec<Cell> {
+    candidates
+        .cells()
+        .cells()
+        .map(|position| Cell {
+            position,
+            digits: candidates.at(position.0, position.1).collect(),
+        })
+        .filter(|cell| cell.digits.len() == count)
+        .collect()
+}
+
+/// Build the step for a wing which removes `digit` from every cell in `removed`.
+fn wing_step(
+    technique: Technique,
+    digit: Digit,
+    removed: Bitfield,
+    pivot: Bitfield,
+    pincers: Bitfield,
+    digits: Vec<Digit>,
+) -> Option<Step> {
+    if removed.is_empty() {
+        return None;
+    }
+
+    let mut eliminations = Candidates::empty();
+    eliminations[digit] = removed;
+    Some(Step {
+        technique,
+        deduction: Deduction::Elimination(eliminations),
+        units: Vec::new(),
+        cells: pivot | pincers,
+        digits,
+        pattern: Pattern::Wing { pivot, pincers },
+    })
+}
+
+/// Find a pivot cell that can only be X or Y, which sees one cell that can only be X or Z, and
+/// another that can only be Y or Z. Whichever the pivot is, one of the pincers must be Z, so Z can
+/// be removed from every cell that sees both pincers.
+pub(super) fn xy_wing(candidates: &Candidates) -> Option<Step> {
+    let bivalue = cells_with(candidates, 2);
+    for pivot in &bivalue {
+        for first in bivalue.iter().filter(|cell| cell.sees(pivot)) {
+            let shared = first
+                .digits
+                .iter()
+                .filter(|digit| pivot.digits.contains(digit))
+                .count();
+            if shared != 1 {
+                continue;
+            }
+
+            let z = *first
+                .digits
+                .iter()
+                .find(|digit| !pivot.digits.contains(digit))
+                .unwrap();
+            let y = *pivot
+                .digits
+                .iter()
+                .find(|digit| !first.digits.contains(digit))
+                .unwrap();
+
+            for second in bivalue.iter().filter(|cell| cell.sees(pivot)) {
+                if !(second.digits.contains(&y) && second.digits.contains(&z)) {
+                    continue;
+                }
+
+                let pincers = first.bitfield() | second.bitfield();
+                let removed = candidates[z] & first.peers() & second.peers() & !pincers;
+                let digits = vec![pivot.digits[0], pivot.digits[1], z];
+                let step = wing_step(
+                    Technique::XYWing,
+                    z,
+                    removed,
+                    pivot.bitfield(),
+                    pincers,
+                    digits,
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Find a pivot cell that can only be X, Y or Z, which sees one cell that can only be X or Z, and
+/// another that can only be Y or Z. One of the three cells must be Z, so Z can be removed from
+/// every cell that sees all three.
+pub(super) fn xyz_wing(candidates: &Candidates) -> Option<Step> {
+    let bivalue = cells_with(candidates, 2);
+    for pivot in cells_with(candidates, 3) {
+        let pincers = bivalue
+            .iter()
+            .filter(|cell| cell.sees(&pivot))
+            .filter(|cell| cell.digits.iter().all(|digit| pivot.digits.contains(digit)))
+            .collect::<Vec<_>>();
+
+        for (index, first) in pincers.iter().enumerate() {
+            for second in &pincers[index + 1..] {
+                let shared = first
+                    .digits
+                    .iter()
+                    .cloned()
+                    .filter(|digit| second.digits.contains(digit))
+                    .collect::<Vec<_>>();
+                let [z] = shared[..] else {
+                    continue;
+                };
+
+                let cells = first.bitfield() | second.bitfield();
+                let removed = candidates[z]
+                    & pivot.peers()
+                    & first.peers()
+                    & second.peers()
+                    & !(cells | pivot.bitfield());
+                let step = wing_step(
+                    Technique::XYZWing,
+                    z,
+                    removed,
+                    pivot.bitfield(),
+                    cells,
+                    pivot.digits.clone(),
+                );
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Find two cells that can only be X or Y, which don't see each other, along with a unit where X
+/// can only go in two places, one seeing each of those cells. They can't both be X, as then X
+/// would have nowhere to go in the unit, so one of them must be Y. Y can then be removed from
+/// every cell that sees both.
+pub(super) fn w_wing(candidates: &Candidates) -> Option<Step> {
+    let bivalue = cells_with(candidates, 2);
+    for (index, first) in bivalue.iter().enumerate() {
+        for second in &bivalue[index + 1..] {
+            if first.digits != second.digits || first.sees(second) {
+                continue;
+            }
+
+            let pincers = first.bitfield() | second.bitfield();
+            for (link, other) in [
+                (first.digits[0], first.digits[1]),
+                (first.digits[1], first.digits[0]),
+            ] {
+                let removed = candidates[other] & first.peers() & second.peers();
+                if removed.is_empty() {
+                    continue;
+                }
+
+                for unit in Unit::iter() {
+                    let ends = candidates[link] & unit.cells();
+                    if ends.len() != 2 || !(ends & pincers).is_empty() {
+                        continue;
+                    }
+
+                    let mut positions = ends.cells();
+                    let (start, end) = (positions.next().unwrap(), positions.next().unwrap());
+                    let start = Bitfield::new(start.0, start.1);
+                    let end = Bitfield::new(end.0, end.1);
+                    let joined = (first.peers().contains(start) && second.peers().contains(end))
+                        || (first.peers().contains(end) && second.peers().contains(start));
+                    if !joined {
+                        continue;
+                    }
+
+                    let step = wing_step(
+                        Technique::WWing,
+                        other,
+                        removed,
+                        ends,
+                        pincers,
+                        vec![link, other],
+                    );
+                    return step.map(|step| Step {
+                        units: vec![unit],
+                        ..step
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{w_wing, xy_wing, xyz_wing};
+    use crate::{
+        logic::{from_cells, Deduction, Pattern, Technique},
+        Bitfield, Candidates, Digit, Unit,
+    };
+
+    #[test]
+    fn finds_xy_wing() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2]),
+            ((0, 5), &[_1, _3]),
+            ((4, 0), &[_2, _3]),
+            ((4, 5), &[_3, _4]),
+        ]);
+        let step = xy_wing(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[_3] = Bitfield::new(4, 5);
+        assert_eq!(step.technique, Technique::XYWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Wing {
+                pivot: Bitfield::new(0, 0),
+                pincers: Bitfield::new(0, 5) | Bitfield::new(4, 0),
+            }
+        );
+    }
+
+    #[test]
+    fn finds_xyz_wing() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_1, _2, _3]),
+            ((0, 4), &[_1, _3]),
+            ((1, 1), &[_2, _3]),
+            ((0, 2), &[_3, _5]),
+        ]);
+        assert!(xy_wing(&candidates).is_none());
+        let step = xyz_wing(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[_3] = Bitfield::new(0, 2);
+        assert_eq!(step.technique, Technique::XYZWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(
+            step.pattern,
+            Pattern::Wing {
+                pivot: Bitfield::new(0, 0),
+                pincers: Bitfield::new(0, 4) | Bitfield::new(1, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn finds_w_wing() {
+        use Digit::*;
+        let candidates = from_cells(&[
+            ((0, 0), &[_4, _7]),
+            ((8, 8), &[_4, _7]),
+            ((4, 0), &[_4, _5, _6]),
+            ((4, 8), &[_4, _5, _6]),
+            ((0, 8), &[_7, _9]),
+            ((8, 0), &[_2, _5, _7]),
+        ]);
+        let step = w_wing(&candidates).unwrap();
+
+        let mut eliminations = Candidates::empty();
+        eliminations[_7] = Bitfield::new(0, 8) | Bitfield::new(8, 0);
+        assert_eq!(step.technique, Technique::WWing);
+        assert_eq!(step.deduction, Deduction::Elimination(eliminations));
+        assert_eq!(step.units, vec![Unit::Row(4)]);
+        assert_eq!(
+            step.pattern,
+            Pattern::Wing {
+                pivot: Bitfield::new(4, 0) | Bitfield::new(4, 8),
+                pincers: Bitfield::new(0, 0) | Bitfield::new(8, 8),
+            }
+        );
+    }
+}
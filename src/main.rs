@@ -1,69 +1,52 @@
-use std::{env, ops::BitOr, process, time::Instant};
-use sudoku::{Bitfield, Board, Digit};
+use std::{env, process, time::Instant};
+use sudoku::{backend, logic, Board};
 
-fn solve_helper(possible_paths: &[Vec<Bitfield>], taken_spaces: Bitfield) -> Option<Vec<Bitfield>> {
-    if let Some((first, rest)) = possible_paths.split_first() {
-        first
-            .iter()
-            .cloned()
-            .filter(|&path| (path & taken_spaces).is_empty())
-            .find_map(|path| {
-                let mut output = solve_helper(rest, path | taken_spaces)?;
-                output.insert(0, path);
-                Some(output)
-            })
-    } else {
-        Some(Vec::new())
-    }
-}
-
-fn solve(board: &mut Board, path_db: &[Bitfield]) -> bool {
-    let total_clues = Digit::iter()
-        .map(|digit| board[digit])
-        .fold(Bitfield::default(), BitOr::bitor);
+const USAGE: &str = "Usage: sudoku [--engine <engine>] <puzzle> [puzzle2] [puzzle3] ...
+       sudoku rate <puzzle> [puzzle2] [puzzle3] ...";
 
-    let possible_paths = Digit::iter()
-        .map(|digit| {
-            let clues = board[digit];
-            let opposing_clues = total_clues & !clues;
+fn main() {
+    let mut args = env::args().skip(1).peekable();
 
-            path_db
-                .iter()
-                .cloned()
-                .filter(|&path| path.contains(clues))
-                .filter(|&path| (path & opposing_clues).is_empty())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+    if args.peek().map(String::as_str) == Some("rate") {
+        args.next();
+        rate(args.collect());
+        return;
+    }
 
-    if let Some(assigned_paths) = solve_helper(&possible_paths, Bitfield::default()) {
-        for (digit, path) in Digit::iter().zip(assigned_paths) {
-            board[digit] = path;
-        }
-        true
+    let engine_name = if args.peek().map(String::as_str) == Some("--engine") {
+        args.next();
+        args.next()
     } else {
-        false
-    }
-}
+        Some(String::from("templates"))
+    };
 
-fn main() {
-    let args = env::args().skip(1);
-    if args.len() == 0 {
-        eprintln!("Usage: sudoku <puzzle> [puzzle2] [puzzle3] [puzzle4] ...");
+    let puzzles = args.collect::<Vec<_>>();
+    let Some(engine_name) = engine_name.filter(|_| !puzzles.is_empty()) else {
+        eprintln!("{}", USAGE);
         process::exit(1);
-    }
+    };
 
-    let all_paths = sudoku::generate_paths().collect::<Vec<_>>();
+    let Some(engine) = backend::engine(&engine_name) else {
+        let names = backend::engines()
+            .iter()
+            .map(|engine| engine.name())
+            .collect::<Vec<_>>();
+        eprintln!(
+            "Unknown engine {:?}, expected one of: {}",
+            engine_name,
+            names.join(", ")
+        );
+        process::exit(1);
+    };
 
-    for puzzle in args {
-        if let Some(mut board) = Board::parse(&puzzle) {
+    for puzzle in puzzles {
+        if let Some(board) = Board::parse(&puzzle) {
             let time = Instant::now();
-            let was_solved = solve(&mut board, &all_paths);
+            let solution = engine.solve(&board);
             let solve_time = time.elapsed();
-            if was_solved {
-                println!("{}", board);
-            } else {
-                println!("No solution found");
+            match solution {
+                Ok(solution) => println!("{}", solution),
+                Err(_) => println!("No solution found"),
             }
             println!("Solution took {:?}", solve_time);
         } else {
@@ -71,3 +54,19 @@ fn main() {
         }
     }
 }
+
+/// Print the difficulty rating of each puzzle.
+fn rate(puzzles: Vec<String>) {
+    if puzzles.is_empty() {
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+
+    for puzzle in puzzles {
+        match Board::parse(&puzzle).map(|board| logic::rate(&board)) {
+            Some(Some(rating)) => println!("{}", rating),
+            Some(None) => eprintln!("Invalid puzzle: it doesn't have exactly one solution"),
+            None => eprintln!("Invalid board format"),
+        }
+    }
+}
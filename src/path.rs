@@ -48,7 +48,7 @@ fn permutations() -> impl Iterator<Item = [usize; 9]> {
 }
 
 /// Create a bitfield that's true for every cell inside a box, and false elsewhere.
-fn new_box(row: usize, col: usize) -> Bitfield {
+pub(crate) fn new_box(row: usize, col: usize) -> Bitfield {
     (0..3)
         .flat_map(|sub_row| {
             (0..3).map(move |sub_col| Bitfield::new(3 * row + sub_row, 3 * col + sub_col))
@@ -56,7 +56,18 @@ fn new_box(row: usize, col: usize) -> Bitfield {
         .fold(Bitfield::default(), BitOr::bitor)
 }
 
-/// Generate all possible "Paths" that are valid within a Sudoku.
+/// Every valid path, as generated by the build script. This is the same list, in the same order, as
+/// `generate_paths` produces.
+static PATHS: [Bitfield; 46_656] = include!(concat!(env!("OUT_DIR"), "/paths.rs"));
+
+/// All possible "Paths" that are valid within a Sudoku. The table is built at compile time, so
+/// unlike `generate_paths` this costs nothing to call.
+pub fn all_paths() -> &'static [Bitfield] {
+    &PATHS
+}
+
+/// Generate all possible "Paths" that are valid within a Sudoku, by filtering every permutation of
+/// columns. Prefer `all_paths`, which looks up the same list without recomputing it.
 pub fn generate_paths() -> impl Iterator<Item = Bitfield> {
     let boxes = [
         new_box(0, 0),
@@ -170,7 +181,7 @@ mod box_test {
 
 #[cfg(test)]
 mod test {
-    use super::generate_paths;
+    use super::{all_paths, generate_paths};
     use crate::bitfield::Bitfield;
 
     #[test]
@@ -179,6 +190,11 @@ mod test {
         assert_eq!(generate_paths().count(), 46_656);
     }
 
+    #[test]
+    fn table_matches_generated_paths() {
+        assert!(generate_paths().eq(all_paths().iter().cloned()));
+    }
+
     #[test]
     fn includes_one_known_value() {
         let example_path = Bitfield::new(0, 1)
@@ -0,0 +1,154 @@
+use crate::{all_paths, Bitfield, Unit};
+use std::{cmp::Ordering, sync::OnceLock};
+
+/// An index over the table of every valid path. For each cell, it stores the list of every path
+/// that occupies that cell, so questions like "which paths pass through these cells" can be
+/// answered by intersecting a few precomputed lists, rather than scanning the whole table.
+pub struct PathDb {
+    paths: &'static [Bitfield],
+    /// For each cell in reading order, the indices into `paths` of every path passing through it,
+    /// in ascending order. There are fewer than 2^16 paths, so the indices fit into a `u16`.
+    by_cell: Vec<Vec<u16>>,
+}
+
+impl PathDb {
+    /// Build the index over the precomputed path table. Prefer `PathDb::shared`, which only does
+    /// this once per process.
+    pub fn new() -> Self {
+        let paths = all_paths();
+        let mut by_cell = vec![Vec::new(); 81];
+        for (index, path) in paths.iter().enumerate() {
+            for (row, col) in path.cells() {
+                by_cell[9 * row + col].push(index as u16);
+            }
+        }
+
+        PathDb { paths, by_cell }
+    }
+
+    /// An index which is shared by the whole process, and built the first time it is needed.
+    pub fn shared() -> &'static PathDb {
+        static DB: OnceLock<PathDb> = OnceLock::new();
+        DB.get_or_init(PathDb::new)
+    }
+
+    /// Every valid path, in the same order as `all_paths`.
+    pub fn paths(&self) -> &'static [Bitfield] {
+        self.paths
+    }
+
+    /// Find every path that passes through all of the `required` cells, and through none of the
+    /// `forbidden` cells.
+    pub fn matching(&self, required: Bitfield, forbidden: Bitfield) -> Vec<Bitfield> {
+        let indices = if required.is_empty() {
+            // Every path passes through exactly one cell of each row, so the paths through the
+            // allowed cells of any one row are all the candidates. Pick the row with the fewest.
+            let allowed = (0..9)
+                .map(|row| Unit::Row(row).cells() & !forbidden)
+                .min_by_key(|cells| cells.len())
+                .unwrap();
+            let mut indices = allowed
+                .cells()
+                .flat_map(|(row, col)| self.by_cell[9 * row + col].iter().cloned())
+                .collect::<Vec<_>>();
+            indices.sort_unstable();
+            indices
+        } else {
+            let mut lists = required
+                .cells()
+                .map(|(row, col)| &self.by_cell[9 * row + col][..]);
+            let first = lists.next().unwrap().to_vec();
+            lists.fold(first, |acc, list| intersect(&acc, list))
+        };
+
+        indices
+            .into_iter()
+            .map(|index| self.paths[index as usize])
+            .filter(|&path| (path & forbidden).is_empty())
+            .collect()
+    }
+}
+
+impl Default for PathDb {
+    fn default() -> Self {
+        PathDb::new()
+    }
+}
+
+/// Intersect two sorted lists of path indices.
+fn intersect(left: &[u16], right: &[u16]) -> Vec<u16> {
+    let mut output = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        match left[i].cmp(&right[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                output.push(left[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::PathDb;
+    use crate::{all_paths, Bitfield};
+
+    fn brute_force(required: Bitfield, forbidden: Bitfield) -> Vec<Bitfield> {
+        all_paths()
+            .iter()
+            .cloned()
+            .filter(|&path| path.contains(required))
+            .filter(|&path| (path & forbidden).is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn unconstrained_matches_everything() {
+        let db = PathDb::shared();
+        assert_eq!(
+            db.matching(Bitfield::default(), Bitfield::default()),
+            all_paths()
+        );
+    }
+
+    #[test]
+    fn paths_through_one_cell() {
+        // Each of the 9 cells in a row is used by an equal share of the paths
+        let db = PathDb::shared();
+        let paths = db.matching(Bitfield::new(4, 4), Bitfield::default());
+        assert_eq!(paths.len(), 46_656 / 9);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let db = PathDb::shared();
+        let cases = [
+            (
+                Bitfield::new(0, 0) | Bitfield::new(4, 5),
+                Bitfield::new(8, 8) | Bitfield::new(7, 1),
+            ),
+            (
+                Bitfield::default(),
+                Bitfield::new(2, 2) | Bitfield::new(3, 3) | Bitfield::new(6, 0),
+            ),
+            (Bitfield::new(1, 7), Bitfield::new(5, 5)),
+            // These two cells share a row, so no path can pass through both
+            (
+                Bitfield::new(3, 0) | Bitfield::new(3, 8),
+                Bitfield::default(),
+            ),
+        ];
+
+        for (required, forbidden) in cases {
+            assert_eq!(
+                db.matching(required, forbidden),
+                brute_force(required, forbidden)
+            );
+        }
+    }
+}
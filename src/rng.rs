@@ -0,0 +1,100 @@
+/// A small pseudo-random number generator, using the SplitMix64 algorithm. It is nowhere near good
+/// enough for anything security related, but it is fast, and the numbers it produces depend only
+/// on its seed, so they are the same on every platform and every run.
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator which will always produce the same numbers for the same seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// The next 64 random bits.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range `0..bound`, with every number equally likely. Numbers from the top of
+    /// the 64 bit range that would favour some results over others are thrown away.
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "can't pick a number below 0");
+
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    /// A number in the range `0.0..1.0`, with every number equally likely.
+    pub(crate) fn fraction(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Put the items into a random order, with every order equally likely.
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index + 1);
+            items.swap(index, other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rng;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn below_stays_in_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 9];
+        for _ in 0..1000 {
+            let value = rng.below(9);
+            assert!(value < 9);
+            seen[value] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn fraction_stays_in_range() {
+        let mut rng = Rng::new(5);
+        let fractions = (0..1000).map(|_| rng.fraction()).collect::<Vec<_>>();
+        assert!(fractions
+            .iter()
+            .all(|&fraction| (0.0..1.0).contains(&fraction)));
+
+        let mean = fractions.iter().sum::<f64>() / 1000.0;
+        assert!((0.45..0.55).contains(&mean));
+    }
+
+    #[test]
+    fn shuffle_keeps_every_item() {
+        let mut rng = Rng::new(3);
+        let mut items = (0..20).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
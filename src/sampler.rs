@@ -0,0 +1,239 @@
+use crate::{rng::Rng, Bitfield, Board, Digit, PathDb};
+
+/// The number of proposals thrown away before the first grid is returned.
+const BURN_IN: usize = 100;
+
+/// The number of proposals made between each grid that is returned.
+const STEPS: usize = 10;
+
+/// Draws completely filled grids, with every valid grid equally likely. The same seed always gives
+/// the same grids, in the same order.
+///
+/// Picking a random path for each digit in turn favours the grids where there were few paths to
+/// pick from along the way. The sampler corrects for this with the Metropolis-Hastings algorithm:
+/// grids are proposed by picking paths at random, and each proposal replaces the current grid with
+/// a probability that depends on how many choices were made to build each of them. In the long
+/// run, this spends the same amount of time on every grid.
+///
+/// Before a grid is returned, its digits are relabelled, and its rows and columns shuffled, at
+/// random, which keeps the grids uniform. Grids that are returned one after another aren't
+/// independent, though: when a proposal is turned down, the previous grid is shuffled and returned
+/// again, and the shuffle can leave it unchanged.
+pub struct GridSampler {
+    rng: Rng,
+    current: Board,
+    weight: f64,
+}
+
+impl GridSampler {
+    /// Start a new sampler. Some proposals are made and thrown away first, so that the first grid
+    /// returned doesn't depend on where the sampler started.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let (current, weight) = propose(&mut rng);
+        let mut sampler = GridSampler {
+            rng,
+            current,
+            weight,
+        };
+        sampler.advance(BURN_IN);
+        sampler
+    }
+
+    fn advance(&mut self, steps: usize) {
+        for _ in 0..steps {
+            let (proposal, weight) = propose(&mut self.rng);
+            if weight >= self.weight || self.rng.fraction() < (weight - self.weight).exp() {
+                self.current = proposal;
+                self.weight = weight;
+            }
+        }
+    }
+}
+
+impl Iterator for GridSampler {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance(STEPS);
+        Some(shuffle(&mut self.rng, &self.current))
+    }
+}
+
+/// Build a grid by picking a random path for each digit in order, from those which don't overlap
+/// with the paths already picked, and starting again whenever a digit has none left. Returns the
+/// grid along with its weight: the natural log of the number of paths there were to pick from at
+/// each step, all multiplied together. Each grid is proposed with a probability inversely
+/// proportional to this. Backtracking would be faster, but would skew the proposals in a way that
+/// the weight doesn't account for.
+fn propose(rng: &mut Rng) -> (Board, f64) {
+    let paths = PathDb::shared().paths();
+    'attempt: loop {
+        let mut grid = Board::empty();
+        let mut weight = 0.0;
+        let mut choices = paths.to_vec();
+        for digit in Digit::iter() {
+            if choices.is_empty() {
+                continue 'attempt;
+            }
+
+            let path = choices[rng.below(choices.len())];
+            weight += (choices.len() as f64).ln();
+            grid[digit] = path;
+            choices.retain(|&other| (other & path).is_empty());
+        }
+        return (grid, weight);
+    }
+}
+
+/// Apply a random symmetry to a grid: relabel the digits, shuffle the bands and the rows within
+/// each band, shuffle the stacks and the columns within each stack, and maybe reflect it along the
+/// diagonal. Each of these maps the valid grids one to one onto the valid grids, so a uniformly
+/// random grid stays uniformly random.
+fn shuffle(rng: &mut Rng, grid: &Board) -> Board {
+    let rows = line_order(rng);
+    let cols = line_order(rng);
+    let transpose = rng.below(2) == 1;
+    let mut labels = Digit::iter().collect::<Vec<_>>();
+    rng.shuffle(&mut labels);
+
+    let mut output = Board::empty();
+    for (digit, &label) in Digit::iter().zip(&labels) {
+        for (row, col) in grid[digit].cells() {
+            let (row, col) = (rows[row], cols[col]);
+            let (row, col) = if transpose { (col, row) } else { (row, col) };
+            output[label] |= Bitfield::new(row, col);
+        }
+    }
+    output
+}
+
+/// A random order for the rows, or columns, of a grid, which keeps the rows of each band together.
+fn line_order(rng: &mut Rng) -> [usize; 9] {
+    let mut bands = [0, 1, 2];
+    rng.shuffle(&mut bands);
+
+    let mut order = [0; 9];
+    for (band, &target) in bands.iter().enumerate() {
+        let mut lines = [0, 1, 2];
+        rng.shuffle(&mut lines);
+        for (line, &offset) in lines.iter().enumerate() {
+            order[3 * band + line] = 3 * target + offset;
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod test {
+    use super::{propose, shuffle, GridSampler};
+    use crate::{all_paths, rng::Rng, Bitfield, Board, Digit, PathDb};
+
+    /// Work out the weight of a grid from scratch, the same way that `propose` does as it goes.
+    fn weight(grid: &Board) -> f64 {
+        let paths = PathDb::shared().paths();
+        let mut taken = Bitfield::default();
+        let mut weight = 0.0;
+        for digit in Digit::iter() {
+            let choices = paths
+                .iter()
+                .filter(|&&path| (path & taken).is_empty())
+                .count();
+            weight += (choices as f64).ln();
+            taken |= grid[digit];
+        }
+        weight
+    }
+
+    fn is_valid_grid(grid: &Board) -> bool {
+        let mut total = Bitfield::default();
+        for digit in Digit::iter() {
+            if !all_paths().contains(&grid[digit]) || !(total & grid[digit]).is_empty() {
+                return false;
+            }
+            total |= grid[digit];
+        }
+        (!total).is_empty()
+    }
+
+    #[test]
+    fn samples_are_valid_grids() {
+        let samples = GridSampler::new(1).take(20).collect::<Vec<_>>();
+        assert!(samples.iter().all(is_valid_grid));
+    }
+
+    #[test]
+    fn same_seed_same_grids() {
+        let first = GridSampler::new(2).take(3).collect::<Vec<_>>();
+        let second = GridSampler::new(2).take(3).collect::<Vec<_>>();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn shuffle_keeps_grids_valid() {
+        let mut rng = Rng::new(3);
+        let (grid, _) = propose(&mut rng);
+        let shuffled = shuffle(&mut rng, &grid);
+        assert!(is_valid_grid(&shuffled));
+        assert_ne!(grid, shuffled);
+    }
+
+    #[test]
+    fn proposals_know_their_weight() {
+        let mut rng = Rng::new(4);
+        for _ in 0..5 {
+            let (grid, expected) = propose(&mut rng);
+            assert!((weight(&grid) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn samples_are_uniform() {
+        // A uniform sample favours the grids with a higher weight, since those are proposed less
+        // often. The average weight over all grids is estimated from raw proposals, by weighting
+        // each one by how much less often it was proposed, and is about 0.75 higher than the
+        // average of the raw proposals themselves.
+        let mut rng = Rng::new(7);
+        let proposed = (0..1000).map(|_| propose(&mut rng).1).collect::<Vec<_>>();
+        let heaviest = proposed.iter().cloned().fold(f64::MIN, f64::max);
+        let total = proposed
+            .iter()
+            .map(|&weight| (weight - heaviest).exp())
+            .sum::<f64>();
+        let expected = proposed
+            .iter()
+            .map(|&weight| (weight - heaviest).exp() * weight)
+            .sum::<f64>()
+            / total;
+        let baseline = proposed.iter().sum::<f64>() / proposed.len() as f64;
+
+        // The grids the sampler visits are checked before they are shuffled, as well as the grids
+        // it returns. Relabelling the digits changes the weight, so the shuffle on its own moves
+        // the returned grids most of the way from the raw proposals to uniform, and only the grids
+        // it visits show whether the Metropolis-Hastings step is doing its job.
+        let samples = 200;
+        let mut sampler = GridSampler::new(7);
+        let (mut visited, mut returned) = (0.0, 0.0);
+        for _ in 0..samples {
+            let grid = sampler.next().unwrap();
+            visited += sampler.weight;
+            returned += weight(&grid);
+        }
+        let visited = visited / samples as f64;
+        let returned = returned / samples as f64;
+
+        assert!(expected > baseline + 0.5, "{} vs {}", expected, baseline);
+        assert!(
+            (visited - expected).abs() < 0.3,
+            "{} vs {}",
+            visited,
+            expected
+        );
+        assert!(
+            (returned - expected).abs() < 0.3,
+            "{} vs {}",
+            returned,
+            expected
+        );
+    }
+}
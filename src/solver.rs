@@ -0,0 +1,321 @@
+use crate::{Bitfield, Board, Digit, PathDb};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::BitOr,
+};
+
+/// The reasons that the solver can fail to produce a solution for a board.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SolveError {
+    /// There is no way to fill in the rest of the board that is consistent with its clues.
+    NoSolution,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::NoSolution => f.write_str("no solution found"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// The result of checking whether a board has exactly one solution.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Uniqueness {
+    /// There is no way to complete the board.
+    Unsolvable,
+    /// The board has exactly one solution, which is included.
+    Unique(Board),
+    /// The board has more than one solution. Two distinct solutions are included as a witness,
+    /// along with every cell in which they disagree.
+    Multiple {
+        first: Board,
+        second: Board,
+        differences: Bitfield,
+    },
+}
+
+impl Uniqueness {
+    /// Does this represent a board with exactly one solution?
+    pub fn is_unique(&self) -> bool {
+        matches!(self, Uniqueness::Unique(_))
+    }
+}
+
+/// A Sudoku solver that works by assigning one "path" to each digit, such that none of the paths
+/// overlap.
+pub struct Solver {
+    db: &'static PathDb,
+}
+
+impl Solver {
+    /// Create a new solver, backed by the shared path database.
+    pub fn new() -> Self {
+        Solver {
+            db: PathDb::shared(),
+        }
+    }
+
+    /// Find a solution to the given board. The input board is left untouched, and the solved
+    /// board is returned as a new value.
+    pub fn solve(&self, board: &Board) -> Result<Board, SolveError> {
+        self.solutions(board).next().ok_or(SolveError::NoSolution)
+    }
+
+    /// Lazily iterate through every solution to the given board. Each solution is only searched
+    /// for when it is asked for, so taking the first few solutions of a puzzle with many of them
+    /// is cheap.
+    pub fn solutions(&self, board: &Board) -> Solutions {
+        let total_clues = Digit::iter()
+            .map(|digit| board[digit])
+            .fold(Bitfield::default(), BitOr::bitor);
+
+        let possible_paths = Digit::iter()
+            .map(|digit| {
+                let clues = board[digit];
+                let opposing_clues = total_clues & !clues;
+
+                (digit, self.db.matching(clues, opposing_clues))
+            })
+            .collect::<Vec<_>>();
+
+        let stack = if possible_paths.iter().any(|(_, paths)| paths.is_empty()) {
+            Vec::new()
+        } else {
+            vec![Frame::new(possible_paths)]
+        };
+
+        Solutions {
+            board: board.clone(),
+            stack,
+        }
+    }
+
+    /// Check whether the given board has exactly one solution. If it has more than one, the first
+    /// two solutions found are returned, so the caller can see where the ambiguity lies.
+    pub fn uniqueness(&self, board: &Board) -> Uniqueness {
+        let mut solutions = self.solutions(board);
+        match (solutions.next(), solutions.next()) {
+            (None, _) => Uniqueness::Unsolvable,
+            (Some(solution), None) => Uniqueness::Unique(solution),
+            (Some(first), Some(second)) => {
+                let differences = first.differences(&second);
+                Uniqueness::Multiple {
+                    first,
+                    second,
+                    differences,
+                }
+            }
+        }
+    }
+
+    /// Count the solutions to the given board, giving up once `limit` solutions have been found.
+    /// Asking for a limit of 2 is enough to tell whether a puzzle has a unique solution.
+    pub fn count_solutions(&self, board: &Board, limit: usize) -> usize {
+        self.solutions(board).take(limit).count()
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+/// One level of the search, in which a single digit is assigned a path.
+struct Frame {
+    /// The digit being assigned at this level.
+    digit: Digit,
+    /// Every path that this digit could take, given the paths assigned at earlier levels.
+    candidates: Vec<Bitfield>,
+    /// The index of the next candidate path to try.
+    next: usize,
+    /// The path currently assigned to this digit.
+    path: Bitfield,
+    /// The digits that are still unassigned, and the paths each of them could take, given the
+    /// paths assigned at earlier levels.
+    rest: Vec<(Digit, Vec<Bitfield>)>,
+}
+
+impl Frame {
+    /// Start a new level of the search, by picking the unassigned digit with the fewest possible
+    /// paths. Digits with few options are the most likely to expose a dead end early, so trying
+    /// them first keeps the search tree small.
+    fn new(mut unassigned: Vec<(Digit, Vec<Bitfield>)>) -> Self {
+        let (most_constrained, _) = unassigned
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, paths))| paths.len())
+            .expect("a frame needs at least one digit to assign");
+        let (digit, candidates) = unassigned.swap_remove(most_constrained);
+
+        Frame {
+            digit,
+            candidates,
+            next: 0,
+            path: Bitfield::default(),
+            rest: unassigned,
+        }
+    }
+}
+
+/// A lazy iterator through every solution to a board. Created by `Solver::solutions`.
+pub struct Solutions {
+    board: Board,
+    stack: Vec<Frame>,
+}
+
+impl Iterator for Solutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.last_mut() {
+            let Some(&path) = frame.candidates.get(frame.next) else {
+                self.stack.pop();
+                continue;
+            };
+            frame.next += 1;
+            frame.path = path;
+
+            if frame.rest.is_empty() {
+                let mut solution = self.board.clone();
+                for frame in &self.stack {
+                    solution[frame.digit] = frame.path;
+                }
+                return Some(solution);
+            }
+
+            let rest = frame
+                .rest
+                .iter()
+                .map(|(digit, paths)| {
+                    let paths = paths
+                        .iter()
+                        .cloned()
+                        .filter(|&other| (other & path).is_empty())
+                        .collect::<Vec<_>>();
+                    (*digit, paths)
+                })
+                .collect::<Vec<_>>();
+
+            if rest.iter().all(|(_, paths)| !paths.is_empty()) {
+                self.stack.push(Frame::new(rest));
+            }
+        }
+
+        None
+    }
+}
+
+/// A solved grid with a rectangle of 2s and 8s cleared, so they can be swapped, which leaves it
+/// with exactly two solutions.
+#[cfg(test)]
+pub(crate) const TWO_SOLUTIONS: &str =
+    "6.194375.7.361549.594728361142879635357461289869532174238197546916354827475286913";
+
+#[cfg(test)]
+mod test {
+    use super::{SolveError, Solver, TWO_SOLUTIONS};
+    use crate::{all_paths, Bitfield, Board, Digit};
+
+    const PUZZLE: &str =
+        "........8..3...4...9..2..6.....79.......612...6.5.2.7...8...5...1.....2.4.5.....3";
+
+    #[test]
+    fn solution_keeps_clues() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let solution = Solver::new().solve(&board).unwrap();
+
+        for digit in Digit::iter() {
+            assert!(solution[digit].contains(board[digit]));
+        }
+    }
+
+    #[test]
+    fn solution_is_complete() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let solution = Solver::new().solve(&board).unwrap();
+
+        let mut total = Bitfield::default();
+        for digit in Digit::iter() {
+            let path = solution[digit];
+            assert!(all_paths().contains(&path));
+            assert!((path & total).is_empty());
+            total |= path;
+        }
+        assert!((!total).is_empty());
+    }
+
+    #[test]
+    fn input_is_not_modified() {
+        let board = Board::parse(PUZZLE).unwrap();
+        let copy = board.clone();
+        Solver::new().solve(&board).unwrap();
+
+        assert_eq!(board, copy);
+    }
+
+    #[test]
+    fn reports_unsolvable_board() {
+        // The top left cell must be a 1, but there is already a 1 further down its column
+        let board = Board::parse(
+            ".23456789..................1.....................................................",
+        )
+        .unwrap();
+
+        assert_eq!(Solver::new().solve(&board), Err(SolveError::NoSolution));
+    }
+
+    #[test]
+    fn counts_unique_solution() {
+        let board = Board::parse(PUZZLE).unwrap();
+        assert_eq!(Solver::new().count_solutions(&board, 2), 1);
+    }
+
+    #[test]
+    fn enumerates_every_solution() {
+        let board = Board::parse(TWO_SOLUTIONS).unwrap();
+        let solutions = Solver::new().solutions(&board).collect::<Vec<_>>();
+
+        assert_eq!(solutions.len(), 2);
+        assert_ne!(solutions[0], solutions[1]);
+        for solution in solutions {
+            for digit in Digit::iter() {
+                assert!(solution[digit].contains(board[digit]));
+            }
+        }
+    }
+
+    #[test]
+    fn solves_sparse_puzzle() {
+        // 17 clues, which is as few as a uniquely solvable puzzle can have
+        let board = Board::parse(
+            "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        )
+        .unwrap();
+        let solution = Solver::new().solve(&board).unwrap();
+
+        for digit in Digit::iter() {
+            assert!(solution[digit].contains(board[digit]));
+            assert_eq!(solution[digit].len(), 9);
+        }
+    }
+
+    #[test]
+    fn count_stops_at_limit() {
+        let board = Board::parse(&".".repeat(81)).unwrap();
+        assert_eq!(Solver::new().count_solutions(&board, 5), 5);
+    }
+
+    #[test]
+    fn unsolvable_board_has_no_solutions() {
+        let board = Board::parse(
+            ".23456789..................1.....................................................",
+        )
+        .unwrap();
+        assert_eq!(Solver::new().count_solutions(&board, 2), 0);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{path::new_box, Bitfield};
+use std::{
+    fmt::{Display, Formatter},
+    ops::BitOr,
+};
+
+/// One of the 27 groups of cells that must contain every digit exactly once. Rows and columns are
+/// numbered from 0 to 8, and boxes are numbered from 0 to 8 in reading order.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Unit {
+    Row(usize),
+    Col(usize),
+    Box(usize),
+}
+
+impl Unit {
+    /// Iterate through all 27 units: the rows, then the columns, then the boxes.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        (0..9)
+            .map(Unit::Row)
+            .chain((0..9).map(Unit::Col))
+            .chain((0..9).map(Unit::Box))
+    }
+
+    /// The three units that contain the cell at position `row`, `col`.
+    pub fn containing(row: usize, col: usize) -> [Self; 3] {
+        [
+            Unit::Row(row),
+            Unit::Col(col),
+            Unit::Box(3 * (row / 3) + col / 3),
+        ]
+    }
+
+    /// Create a bitfield that's true for every cell inside this unit, and false elsewhere.
+    pub fn cells(self) -> Bitfield {
+        match self {
+            Unit::Row(row) => (0..9)
+                .map(|col| Bitfield::new(row, col))
+                .fold(Bitfield::default(), BitOr::bitor),
+            Unit::Col(col) => (0..9)
+                .map(|row| Bitfield::new(row, col))
+                .fold(Bitfield::default(), BitOr::bitor),
+            Unit::Box(square) => new_box(square / 3, square % 3),
+        }
+    }
+}
+
+/// Every cell that shares a row, column or box with the cell at `row`, `col`, not including the
+/// cell itself.
+pub fn peers(row: usize, col: usize) -> Bitfield {
+    let units = Unit::containing(row, col)
+        .into_iter()
+        .map(Unit::cells)
+        .fold(Bitfield::default(), BitOr::bitor);
+    units & !Bitfield::new(row, col)
+}
+
+// Units are printed for people, so they are numbered from 1 rather than 0.
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unit::Row(row) => write!(f, "row {}", row + 1),
+            Unit::Col(col) => write!(f, "column {}", col + 1),
+            Unit::Box(square) => write!(f, "box {}", square + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{peers, Unit};
+    use crate::Bitfield;
+
+    #[test]
+    fn every_unit_has_nine_cells() {
+        assert_eq!(Unit::iter().count(), 27);
+        assert!(Unit::iter().all(|unit| unit.cells().len() == 9));
+    }
+
+    #[test]
+    fn containing_units_hold_the_cell() {
+        let cell = Bitfield::new(4, 7);
+        let units = Unit::containing(4, 7);
+
+        assert_eq!(units, [Unit::Row(4), Unit::Col(7), Unit::Box(5)]);
+        assert!(units.iter().all(|unit| unit.cells().contains(cell)));
+    }
+
+    #[test]
+    fn peer_count() {
+        let cell_peers = peers(4, 7);
+        assert_eq!(cell_peers.len(), 20);
+        assert!(!cell_peers.contains(Bitfield::new(4, 7)));
+        assert!(cell_peers.contains(Bitfield::new(3, 6)));
+        assert!(cell_peers.contains(Bitfield::new(0, 7)));
+    }
+
+    #[test]
+    fn display_is_one_based() {
+        assert_eq!(Unit::Row(0).to_string(), "row 1");
+        assert_eq!(Unit::Col(8).to_string(), "column 9");
+        assert_eq!(Unit::Box(4).to_string(), "box 5");
+    }
+}